@@ -0,0 +1,96 @@
+//! Compares per-agent A* against a shared flow field on a generated street-grid map.
+//!
+//! Run with `cargo bench --bench navigation [-- seed]`.
+use std::collections::{HashMap, HashSet};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use pathfinding::grid::Grid;
+use aliens_vs_suburbia::general::components::map_components::DecorationDensity;
+use aliens_vs_suburbia::general::resources::map_resources::MapGraph;
+use aliens_vs_suburbia::map::flow_field::FlowField;
+use aliens_vs_suburbia::map::map_generator;
+
+const AGENT_COUNTS: [usize; 3] = [50, 200, 1000];
+/// Runs per measurement; the median is reported.
+const SAMPLES: usize = 15;
+
+fn main() {
+    // `cargo bench` passes `--bench`; the first number is the seed.
+    let seed = std::env::args().skip(1).find_map(|s| s.parse().ok()).unwrap_or(7);
+    let map = map_generator::generate_street_grid_map(seed, 26, 38, &DecorationDensity::default());
+    let mut map_graph = MapGraph {
+        path_finding_grid: Grid::new(0, 0),
+        occupied_tiles: HashSet::new(),
+        goal: (0, 0),
+        spawn_points: Vec::new(),
+        terrain: HashMap::new(),
+    };
+    map_graph.load_map(&map);
+    let floor: Vec<(usize, usize)> = map_graph.path_finding_grid.iter().collect();
+    println!(
+        "street grid seed {seed}: {}x{} tiles, {} walkable, goal {:?}, median of {SAMPLES} runs",
+        map_graph.path_finding_grid.width,
+        map_graph.path_finding_grid.height,
+        floor.len(),
+        map_graph.goal,
+    );
+
+    let build = median(|| {
+        black_box(FlowField::build(&map_graph));
+    });
+    let field = FlowField::build(&map_graph);
+
+    for count in AGENT_COUNTS {
+        // Spread agents evenly over the walkable tiles so runs are repeatable.
+        let agents: Vec<(usize, usize)> = (0..count).map(|i| floor[i * 7919 % floor.len()]).collect();
+
+        let astar = median(|| {
+            for agent in &agents {
+                black_box(map_graph.find_path(*agent, map_graph.goal));
+            }
+        });
+        let flow = median(|| {
+            let field = FlowField::build(&map_graph);
+            for agent in &agents {
+                let mut tile = *agent;
+                while let Some(next) = field.next_tile(tile) {
+                    tile = next;
+                }
+                black_box(tile);
+            }
+        });
+
+        let astar_steps: usize = agents.iter()
+            .filter_map(|agent| map_graph.find_path(*agent, map_graph.goal))
+            .map(|(path, _)| path.len())
+            .sum();
+        let field_steps: usize = agents.iter()
+            .map(|agent| std::iter::successors(Some(*agent), |tile| field.next_tile(*tile)).count())
+            .sum();
+
+        println!(
+            "{count:>5} agents  a*: {:>9}  flow field: {:>9} (build {:>9})  steps {astar_steps}/{field_steps}",
+            fmt(astar),
+            fmt(flow),
+            fmt(build),
+        );
+    }
+}
+
+/// Median wall time of `SAMPLES` runs of `f`, after one warm-up run.
+fn median(mut f: impl FnMut()) -> Duration {
+    f();
+    let mut times: Vec<Duration> = (0..SAMPLES)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+        .collect();
+    times.sort();
+    times[SAMPLES / 2]
+}
+
+fn fmt(d: Duration) -> String {
+    format!("{:.3}ms", d.as_secs_f64() * 1000.0)
+}
@@ -1,9 +1,16 @@
 use bevy::app::{App, FixedUpdate, Plugin, Update};
 use bevy::prelude::{in_state, IntoScheduleConfigs};
 use crate::ai::components::move_towards_goal_components::{AgentReachedGoal, AgentCannotFindPath};
-use crate::ai::systems::approach_and_attack_player_systems::{approach_player_system, attack_player_system, can_agent_see_player_system};
+use crate::ai::systems::alien_behavior_systems::{flee_system, select_alien_behavior};
+use crate::ai::components::senses_components::Noise;
+use crate::ai::systems::approach_and_attack_player_systems::{approach_player_system, attack_player_system, can_agent_see_player_system, investigate_system};
 use crate::ai::systems::avoid_walls_systems::{avoid_walls_action_system, avoid_walls_data_system};
-use crate::ai::systems::destroy_the_map_systems::{agent_cant_find_path, destroy_the_map_action_system};
+use crate::ai::systems::destroy_the_map_systems::{agent_cant_find_path, destroy_the_map_action_system, replan_on_grid_change};
+use crate::ai::systems::move_forward_systems::move_forward_system;
+use crate::ai::systems::fly_to_goal_systems::fly_to_goal_system;
+use crate::ai::systems::ranged_attack_systems::{ranged_target_system, spit_system};
+use crate::ai::systems::senses_systems::hear_noise_system;
+use crate::general::systems::map_systems::update_flow_field;
 use crate::ai::systems::move_towards_goal_systems::{agent_reached_goal_handler, move_towards_goal_system};
 use crate::game_state::GameState;
 #[allow(dead_code)]
@@ -14,6 +21,7 @@ impl Plugin for AiPlugin {
         app
             .add_message::<AgentReachedGoal>()
             .add_message::<AgentCannotFindPath>()
+            .add_message::<Noise>()
             .add_systems(
                 Update,
                 (
@@ -31,11 +39,30 @@ impl Plugin for AiPlugin {
             .add_systems(
                 Update,
                 (
-                    avoid_walls_action_system,
-                    approach_player_system,
-                    attack_player_system,
-                    move_towards_goal_system,
-                    destroy_the_map_action_system,
+                    hear_noise_system.before(select_alien_behavior),
+                    ranged_target_system.before(select_alien_behavior),
+                    select_alien_behavior,
+                    (
+                        move_forward_system,
+                        avoid_walls_action_system
+                            .after(move_forward_system)
+                            .after(approach_player_system)
+                            .after(flee_system)
+                            .after(investigate_system),
+                        approach_player_system,
+                        attack_player_system,
+                        flee_system,
+                        investigate_system,
+                        spit_system,
+                        move_towards_goal_system
+                            .before(agent_reached_goal_handler)
+                            .before(agent_cant_find_path),
+                        replan_on_grid_change
+                            .after(update_flow_field)
+                            .before(destroy_the_map_action_system),
+                        destroy_the_map_action_system,
+                    ).after(select_alien_behavior),
+                    fly_to_goal_system.before(agent_reached_goal_handler),
                 ),
             );
     }
@@ -48,6 +75,7 @@ impl Plugin for StatefulAiPlugin {
         app
             .add_message::<AgentReachedGoal>()
             .add_message::<AgentCannotFindPath>()
+            .add_message::<Noise>()
             .add_systems(
                 Update,
                 (
@@ -65,11 +93,30 @@ impl Plugin for StatefulAiPlugin {
             .add_systems(
                 Update,
                 (
-                    avoid_walls_action_system,
-                    approach_player_system,
-                    attack_player_system,
-                    move_towards_goal_system,
-                    destroy_the_map_action_system,
+                    hear_noise_system.before(select_alien_behavior),
+                    ranged_target_system.before(select_alien_behavior),
+                    select_alien_behavior,
+                    (
+                        move_forward_system,
+                        avoid_walls_action_system
+                            .after(move_forward_system)
+                            .after(approach_player_system)
+                            .after(flee_system)
+                            .after(investigate_system),
+                        approach_player_system,
+                        attack_player_system,
+                        flee_system,
+                        investigate_system,
+                        spit_system,
+                        move_towards_goal_system
+                            .before(agent_reached_goal_handler)
+                            .before(agent_cant_find_path),
+                        replan_on_grid_change
+                            .after(update_flow_field)
+                            .before(destroy_the_map_action_system),
+                        destroy_the_map_action_system,
+                    ).after(select_alien_behavior),
+                    fly_to_goal_system.before(agent_reached_goal_handler),
                 ).run_if(in_state(GameState::InGame)),
             );
     }
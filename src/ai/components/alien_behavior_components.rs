@@ -0,0 +1,119 @@
+use bevy::prelude::Component;
+use bevy::reflect::Reflect;
+
+/// Score bonus the active behaviour gets, so two close scores don't flip back and forth.
+pub const BEHAVIOR_HYSTERESIS: f32 = 0.1;
+
+/// What an alien is doing right now. Only the active behaviour's system steers it, though
+/// `avoid_walls_action_system` may turn it away from a wall afterwards; see `avoids_walls`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum AlienBehaviorState {
+    /// Nothing better to do: walk straight ahead.
+    #[default]
+    Wander,
+    /// Follow the flow field to the goal.
+    SeekGoal,
+    /// Break through the obstacle picked by `MustDestroyTheMap`.
+    Breach,
+    /// Go to where a player was last seen or heard.
+    Investigate,
+    /// Go for the player in sight.
+    ChasePlayer,
+    /// Stand and shoot at a player or breach target within range.
+    Spit,
+    /// Run from the nearest player while badly hurt.
+    Flee,
+}
+
+impl AlienBehaviorState {
+    pub const ALL: [AlienBehaviorState; 7] = [
+        AlienBehaviorState::Wander,
+        AlienBehaviorState::SeekGoal,
+        AlienBehaviorState::Breach,
+        AlienBehaviorState::Investigate,
+        AlienBehaviorState::ChasePlayer,
+        AlienBehaviorState::Spit,
+        AlienBehaviorState::Flee,
+    ];
+
+    /// Behaviours that head straight for a point without a path around walls, so wall
+    /// avoidance steers on top of them. Path followers and standing behaviours are left alone.
+    pub fn avoids_walls(&self) -> bool {
+        matches!(self,
+            AlienBehaviorState::Wander
+            | AlienBehaviorState::ChasePlayer
+            | AlienBehaviorState::Flee)
+    }
+
+    /// How much the alien wants this behaviour given what it knows, from 0 (can't) to 1.
+    pub fn score(&self, senses: &BehaviorSenses) -> f32 {
+        match self {
+            AlienBehaviorState::Wander => 0.1,
+            AlienBehaviorState::SeekGoal => if senses.can_seek_goal { 0.5 } else { 0.0 },
+            AlienBehaviorState::Breach => if senses.breaching { 0.6 } else { 0.0 },
+            AlienBehaviorState::Investigate => if senses.remembers_player { 0.65 } else { 0.0 },
+            AlienBehaviorState::ChasePlayer => if senses.sees_player { 0.8 } else { 0.0 },
+            AlienBehaviorState::Spit => if senses.target_in_range { 0.9 } else { 0.0 },
+            AlienBehaviorState::Flee => {
+                if senses.player_near && senses.health_fraction < senses.flee_below {
+                    // The closer to death, the more it wants out.
+                    0.85 + 0.15 * (1.0 - senses.health_fraction / senses.flee_below)
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+/// What the selector knows about one alien this frame.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BehaviorSenses {
+    pub can_seek_goal: bool,
+    pub breaching: bool,
+    pub sees_player: bool,
+    pub remembers_player: bool,
+    pub target_in_range: bool,
+    pub player_near: bool,
+    pub health_fraction: f32,
+    pub flee_below: f32,
+}
+
+/// The behaviour selector. `select_alien_behavior` rescores every option each frame
+/// and switches when another one beats the active one by more than `BEHAVIOR_HYSTERESIS`.
+#[derive(Clone, Component, Debug, Default, Reflect)]
+pub struct AlienBehavior {
+    pub state: AlienBehaviorState,
+    /// Seconds since the last switch.
+    pub time_in_state: f32,
+    /// Health fraction below which the alien flees from nearby players; 0 never flees.
+    pub flee_below: f32,
+}
+
+impl AlienBehavior {
+    pub fn new(flee_below: f32) -> Self {
+        Self {
+            flee_below,
+            ..Self::default()
+        }
+    }
+
+    /// The highest-scoring behaviour, keeping the active one on a near tie.
+    /// One that scores 0 is dropped regardless.
+    pub fn best(&self, senses: &BehaviorSenses) -> AlienBehaviorState {
+        let current = match self.state.score(senses) {
+            score if score > 0.0 => score + BEHAVIOR_HYSTERESIS,
+            _ => 0.0,
+        };
+        AlienBehaviorState::ALL
+            .into_iter()
+            .map(|state| (state, state.score(senses)))
+            .filter(|(_, score)| *score > current)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map_or(self.state, |(state, _)| state)
+    }
+
+    pub fn is(&self, state: AlienBehaviorState) -> bool {
+        self.state == state
+    }
+}
@@ -1,3 +1,4 @@
+use bevy::math::Vec3;
 use bevy::prelude::{Component, Entity};
 use bevy::reflect::Reflect;
 
@@ -5,6 +6,29 @@ use bevy::reflect::Reflect;
 pub struct ApproachAndAttackPlayerData {
     pub seen_player: Option<Entity>,
     pub attack_distance: f32,
+    /// Where a player was last seen or heard. Kept after losing sight so the alien can go and look.
+    pub last_known_position: Option<Vec3>,
+    /// Seconds until `last_known_position` is forgotten.
+    pub memory_left: f32,
+    /// What `memory_left` is reset to on every sighting or noise.
+    pub memory_seconds: f32,
+}
+
+impl ApproachAndAttackPlayerData {
+    pub fn remember(&mut self, position: Vec3) {
+        self.last_known_position = Some(position);
+        self.memory_left = self.memory_seconds;
+    }
+
+    pub fn forget(&mut self) {
+        self.last_known_position = None;
+        self.memory_left = 0.0;
+    }
+
+    /// Has somewhere to look, but no player in sight.
+    pub fn should_investigate(&self) -> bool {
+        self.seen_player.is_none() && self.last_known_position.is_some()
+    }
 }
 
 impl Default for ApproachAndAttackPlayerData {
@@ -12,6 +36,9 @@ impl Default for ApproachAndAttackPlayerData {
         Self {
             seen_player: None,
             attack_distance: 0.5,
+            last_known_position: None,
+            memory_left: 0.0,
+            memory_seconds: 5.0,
         }
     }
 }
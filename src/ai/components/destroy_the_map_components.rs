@@ -10,6 +10,13 @@ pub enum MustDestroyTheMapState {
     Failed,
 }
 
+/// Aliens that break obstacles when they can't reach the goal; others stay stuck.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Wrecker {
+    /// Damage per hit on an obstacle.
+    pub damage: i32,
+}
+
 #[derive(Component)]
 pub struct MustDestroyTheMap {
     pub path_of_destruction: Option<Vec<(usize, usize)>>,
@@ -17,16 +24,18 @@ pub struct MustDestroyTheMap {
     pub target_tile: Option<(usize, usize)>,
     pub attack_cooldown: f32,
     pub attack_rate_per_minute: f32,
+    pub damage: i32,
 }
 
 impl MustDestroyTheMap {
-    pub fn new() -> Self {
+    pub fn new(damage: i32) -> Self {
         Self {
             path_of_destruction: None,
             state: MustDestroyTheMapState::NotStarted,
             target_tile: None,
             attack_cooldown: 0.0,
             attack_rate_per_minute: 30.0,
+            damage,
         }
     }
 }
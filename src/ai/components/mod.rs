@@ -1,5 +1,7 @@
 pub(crate) mod avoid_wall_components;
-pub(crate) mod move_forward_components;
+pub(crate) mod alien_behavior_components;
 pub(crate) mod approach_and_attack_player_components;
 pub(crate) mod move_towards_goal_components;
 pub(crate) mod destroy_the_map_components;
+pub(crate) mod senses_components;
+pub(crate) mod ranged_attack_components;
@@ -1 +0,0 @@
-// move_forward_components.rs — no big-brain; behavior selected by AlienBehavior state machine
@@ -1,10 +1,9 @@
 use bevy::prelude::{Component, Entity, Message};
 use bevy::reflect::Reflect;
 
-#[derive(Clone, Component, Debug, Reflect)]
-pub struct MoveTowardsGoalData {
-    pub path: Option<Vec<(usize, usize)>>,
-}
+/// Aliens with this component walk the shared `FlowField` toward the goal.
+#[derive(Clone, Component, Debug, Default, Reflect)]
+pub struct MoveTowardsGoalData;
 
 #[derive(Message, Clone)]
 pub struct AgentReachedGoal(pub Entity);
@@ -0,0 +1,48 @@
+use bevy::math::Vec3;
+use bevy::prelude::{Component, Entity};
+use crate::alien::alien_defs::AlienRanged;
+
+/// An alien that stops at `range` and shoots. `ranged_target_system` keeps `target` and
+/// `aim_point` pointed at the player in sight, or else the obstacle being breached.
+#[derive(Component, Clone, Debug)]
+pub struct RangedAttack {
+    pub range: f32,
+    pub rate_of_fire_per_minute: f32,
+    pub projectile: String,
+    pub damage: i32,
+    pub cool_down: f32,
+    pub target: Option<Entity>,
+    pub aim_point: Option<Vec3>,
+}
+
+impl RangedAttack {
+    pub fn new(ranged: &AlienRanged) -> Self {
+        Self {
+            range: ranged.range,
+            rate_of_fire_per_minute: ranged.fire_rate_per_minute,
+            projectile: ranged.projectile.clone(),
+            damage: ranged.damage,
+            cool_down: 0.0,
+            target: None,
+            aim_point: None,
+        }
+    }
+
+    pub fn has_target(&self) -> bool {
+        self.aim_point.is_some()
+    }
+
+    /// Counts down to the next shot, whether or not the alien is in a position to take it.
+    pub fn reload(&mut self, delta_seconds: f32) {
+        self.cool_down = (self.cool_down - delta_seconds).max(0.0);
+    }
+
+    /// Takes the shot if reloaded, starting the next countdown.
+    pub fn fire(&mut self) -> bool {
+        if self.cool_down > 0.0 {
+            return false;
+        }
+        self.cool_down = 60.0 / self.rate_of_fire_per_minute;
+        true
+    }
+}
@@ -0,0 +1,17 @@
+use bevy::math::Vec3;
+use bevy::prelude::Message;
+
+/// How far a player's throw carries.
+pub const THROW_NOISE_RADIUS: f32 = 3.0;
+/// How far a projectile hitting something carries.
+pub const IMPACT_NOISE_RADIUS: f32 = 2.0;
+/// How far building a piece carries.
+pub const BUILD_NOISE_RADIUS: f32 = 4.0;
+
+/// Something loud happened at `position`. Aliens that chase players and are within `radius`
+/// go and look, unless they can already see a player.
+#[derive(Message, Clone, Debug)]
+pub struct Noise {
+    pub position: Vec3,
+    pub radius: f32,
+}
@@ -0,0 +1,94 @@
+use bevy::prelude::*;
+use avian3d::prelude::{Position, Rotation};
+use crate::ai::components::alien_behavior_components::{AlienBehavior, AlienBehaviorState, BehaviorSenses};
+use crate::ai::components::approach_and_attack_player_components::ApproachAndAttackPlayerData;
+use crate::ai::components::destroy_the_map_components::MustDestroyTheMap;
+use crate::ai::components::move_towards_goal_components::MoveTowardsGoalData;
+use crate::ai::components::ranged_attack_components::RangedAttack;
+use crate::alien::components::general::{Alien, AlienSightShape};
+use crate::control::components::{CharacterControl, ControlDirection, ControlRotation};
+use crate::general::components::Health;
+use crate::player::components::Player;
+
+/// Scores every behaviour for every alien and switches to the best one. A switch hands the
+/// alien's controls over clean, so nothing the previous behaviour held down carries over.
+#[allow(clippy::type_complexity)]
+pub fn select_alien_behavior(
+    time: Res<Time>,
+    mut alien_query: Query<(
+        &mut AlienBehavior,
+        &mut CharacterControl,
+        &Position,
+        &Health,
+        Option<&ApproachAndAttackPlayerData>,
+        Option<&AlienSightShape>,
+        Option<&RangedAttack>,
+        Has<MoveTowardsGoalData>,
+        Has<MustDestroyTheMap>,
+    ), With<Alien>>,
+    player_query: Query<&Position, With<Player>>,
+) {
+    for (mut behavior,
+         mut controller,
+         position,
+         health,
+         approach_player_data,
+         sight_shape,
+         ranged,
+         seeks_goal,
+         breaching,
+    ) in alien_query.iter_mut() {
+        behavior.time_in_state += time.delta_secs();
+
+        let sight_range = sight_shape.map_or(0.0, |sight| sight.range);
+        let senses = BehaviorSenses {
+            can_seek_goal: seeks_goal && !breaching,
+            breaching,
+            sees_player: approach_player_data.is_some_and(|data| data.seen_player.is_some()),
+            remembers_player: approach_player_data.is_some_and(|data| data.should_investigate()),
+            target_in_range: ranged.is_some_and(|ranged| ranged.has_target()),
+            player_near: player_query.iter().any(|player| player.0.distance(position.0) <= sight_range),
+            health_fraction: health.health as f32 / health.max_health.max(1) as f32,
+            flee_below: behavior.flee_below,
+        };
+
+        let best = behavior.best(&senses);
+        if best != behavior.state {
+            behavior.state = best;
+            behavior.time_in_state = 0.0;
+            controller.rotations.clear();
+            controller.directions.clear();
+            controller.speed = controller.max_speed;
+            controller.turn_speed = controller.max_turn_speed;
+        }
+    }
+}
+
+/// Turns the alien's back on the nearest player and runs.
+pub fn flee_system(
+    mut alien_query: Query<(&AlienBehavior, &mut CharacterControl, &Position, &Rotation), With<Alien>>,
+    player_query: Query<&Position, With<Player>>,
+) {
+    for (behavior, mut controller, alien_position, alien_rotation) in alien_query.iter_mut() {
+        if !behavior.is(AlienBehaviorState::Flee) {
+            continue;
+        }
+        let Some(nearest) = player_query.iter()
+            .min_by(|a, b| a.0.distance_squared(alien_position.0).total_cmp(&b.0.distance_squared(alien_position.0)))
+        else {
+            continue;
+        };
+        let away = alien_position.0.xz() - nearest.0.xz();
+        let alien_direction = alien_rotation.0.mul_vec3(Vec3::new(0.0, 0.0, -1.0)).xz();
+        let angle = alien_direction.angle_to(away).to_degrees();
+
+        controller.rotations.clear();
+        controller.directions.clear();
+        if angle.abs() > 5.0 {
+            controller.rotations.insert(if angle > 0.0 { ControlRotation::Right } else { ControlRotation::Left });
+        }
+        if angle.abs() < 45.0 {
+            controller.directions.insert(ControlDirection::Forward);
+        }
+    }
+}
@@ -1,17 +1,36 @@
 use bevy::prelude::*;
 use avian3d::prelude::{Position, Rotation, SpatialQuery, SpatialQueryFilter};
 use bevy::math::Vec2;
+use crate::ai::components::alien_behavior_components::{AlienBehavior, AlienBehaviorState};
 use crate::ai::components::approach_and_attack_player_components::ApproachAndAttackPlayerData;
 use crate::general::components::{Attack, CollisionLayer, Health};
+use crate::general::components::map_components::CurrentTile;
+use crate::general::resources::map_resources::MapGraph;
+use crate::general::systems::map_systems::{world_to_tile, TileDefinitions};
 use crate::alien::components::general::{Alien, AlienSightShape};
+use crate::building::systems::ToWorldCoordinates;
 use crate::control::components::{ControlDirection, CharacterControl, ControlRotation};
 use crate::player::components::Player;
+use crate::status_effects::components::StatusEffects;
 
+/// How close counts as having reached the spot being investigated.
+const INVESTIGATE_ARRIVAL_DISTANCE: f32 = 0.25;
+
+/// Sweeps the sight cone for players, then checks that no wall stands between. A player
+/// out of sight is remembered where last seen for `memory_seconds`.
 pub fn can_agent_see_player_system(
+    time: Res<Time>,
     mut approach_player_query: Query<(&mut ApproachAndAttackPlayerData, &AlienSightShape, &Position, &Rotation)>,
+    player_query: Query<&Position, With<Player>>,
     spatial_query: SpatialQuery,
 ) {
     for (mut alien_brain, sight_shape, position, rotation) in approach_player_query.iter_mut() {
+        if alien_brain.last_known_position.is_some() {
+            alien_brain.memory_left -= time.delta_secs();
+            if alien_brain.memory_left <= 0.0 {
+                alien_brain.forget();
+            }
+        }
         let direction = rotation.0.mul_vec3(Vec3::new(0.0, 0.0, -1.0));
 
         match spatial_query.cast_shape(
@@ -29,17 +48,82 @@ pub fn can_agent_see_player_system(
                 alien_brain.seen_player = None;
             }
             Some(hit_data) => {
-                alien_brain.seen_player = Some(hit_data.entity);
+                let player_position = player_query.get(hit_data.entity).map_or(hit_data.point1, |p| p.0);
+                if has_line_of_sight(&spatial_query, position.0, player_position) {
+                    alien_brain.seen_player = Some(hit_data.entity);
+                    alien_brain.remember(player_position);
+                } else {
+                    alien_brain.seen_player = None;
+                }
             }
         }
     }
 }
 
+/// No `Impassable` collider between `from` and `to`.
+fn has_line_of_sight(spatial_query: &SpatialQuery, from: Vec3, to: Vec3) -> bool {
+    let offset = to - from;
+    let Ok(direction) = Dir3::new(offset) else { return true };
+    spatial_query.cast_ray(
+        from,
+        direction,
+        offset.length(),
+        true,
+        &SpatialQueryFilter::from_mask([CollisionLayer::Impassable]),
+    ).is_none()
+}
+
+/// Walks to where a player was last seen or heard, along the shortest walkable path to that
+/// tile and straight in once there. Arriving with nobody there, or finding no way there, ends
+/// the search.
+pub fn investigate_system(
+    map_graph: Res<MapGraph>,
+    tile_definitions: Res<TileDefinitions>,
+    mut alien_query: Query<(&AlienBehavior, &mut ApproachAndAttackPlayerData, &mut CharacterControl, &Position, &Rotation, &CurrentTile), With<Alien>>,
+) {
+    for (behavior, mut alien_brain, mut controller, alien_position, alien_rotation, current_tile) in alien_query.iter_mut() {
+        if !behavior.is(AlienBehaviorState::Investigate) {
+            continue;
+        }
+        let Some(target) = alien_brain.last_known_position else { continue };
+        controller.rotations.clear();
+        controller.directions.clear();
+        if (target.xz() - alien_position.0.xz()).length() < INVESTIGATE_ARRIVAL_DISTANCE {
+            alien_brain.forget();
+            continue;
+        }
+        let target_tile = world_to_tile(target, &tile_definitions);
+        let waypoint = if current_tile.tile == target_tile {
+            target.xz()
+        } else {
+            match map_graph.find_path(current_tile.tile, target_tile) {
+                Some((path, _)) if path.len() > 1 => path[1].to_world_coords(&tile_definitions).xz(),
+                _ => {
+                    alien_brain.forget();
+                    continue;
+                }
+            }
+        };
+        let alien_direction = alien_rotation.0.mul_vec3(Vec3::new(0.0, 0.0, -1.0)).xz();
+        let angle = alien_direction.angle_to(waypoint - alien_position.0.xz()).to_degrees();
+        controller.speed = controller.max_speed * map_graph.terrain_at(current_tile.tile).speed_factor();
+        if angle.abs() > 5.0 {
+            controller.rotations.insert(if angle > 0.0 { ControlRotation::Right } else { ControlRotation::Left });
+        }
+        if angle.abs() < 30.0 {
+            controller.directions.insert(ControlDirection::Forward);
+        }
+    }
+}
+
 pub fn approach_player_system(
-    mut alien_query: Query<(&ApproachAndAttackPlayerData, &mut CharacterControl, &Position, &Rotation), With<Alien>>,
+    mut alien_query: Query<(&AlienBehavior, &ApproachAndAttackPlayerData, &mut CharacterControl, &Position, &Rotation), With<Alien>>,
     player_query: Query<&Position, With<Player>>,
 ) {
-    for (approach_player_data, mut controller, alien_position, alien_rotation) in alien_query.iter_mut() {
+    for (behavior, approach_player_data, mut controller, alien_position, alien_rotation) in alien_query.iter_mut() {
+        if !behavior.is(AlienBehaviorState::ChasePlayer) {
+            continue;
+        }
         if let Some(player_entity) = approach_player_data.seen_player {
             let alien_direction_vector3 = alien_rotation.0.mul_vec3(Vec3::new(0.0, 0.0, -1.0));
             let alien_direction_vector2 = Vec2::new(alien_direction_vector3.x, alien_direction_vector3.z);
@@ -61,16 +145,19 @@ pub fn approach_player_system(
     }
 }
 
+/// Hurts the player in reach while chasing. Only deals damage; `approach_player_system` steers.
 pub fn attack_player_system(
-    mut alien_query: Query<(&ApproachAndAttackPlayerData, &mut CharacterControl, &Position, &Attack), With<Alien>>,
+    alien_query: Query<(&AlienBehavior, &ApproachAndAttackPlayerData, &Position, &Attack, Option<&StatusEffects>), With<Alien>>,
     mut player_query: Query<(&mut Health, &Position), With<Player>>,
 ) {
-    for (attack_player_data, mut controller, alien_position, alien_attack) in alien_query.iter_mut() {
+    for (behavior, attack_player_data, alien_position, alien_attack, status_effects) in alien_query.iter() {
+        if !behavior.is(AlienBehaviorState::ChasePlayer) || status_effects.is_some_and(StatusEffects::stunned) {
+            continue;
+        }
         if let Some(player_entity) = attack_player_data.seen_player {
             let alien_position_vector2 = Vec2::new(alien_position.0.x, alien_position.0.z);
             if let Ok((mut player_health, player_position)) = player_query.get_mut(player_entity) {
                 let player_position_vector2 = Vec2::new(player_position.0.x, player_position.0.z);
-                controller.rotations.clear();
                 let distance = (player_position_vector2 - alien_position_vector2).length();
                 if distance < attack_player_data.attack_distance * 2.0 {
                     player_health.health -= alien_attack.damage_range;
@@ -2,6 +2,7 @@ use bevy::prelude::{Query, Res};
 use avian3d::prelude::{Position, Rotation, SpatialQuery, SpatialQueryFilter};
 use bevy::math::{EulerRot, Quat, Vec3};
 use bevy::time::Time;
+use crate::ai::components::alien_behavior_components::AlienBehavior;
 use crate::ai::components::avoid_wall_components::AvoidWallsData;
 use crate::control::components::{CharacterControl, ControlRotation};
 use crate::general::components::CollisionLayer;
@@ -59,13 +60,20 @@ pub fn avoid_walls_data_system(
     }
 }
 
+/// Turns an alien away from the wall right in front and slows it down, on top of whatever
+/// its behaviour steered this frame. Only for behaviours that `avoids_walls`.
 pub fn avoid_walls_action_system(
     res: Res<Time>,
-    mut actor_query: Query<(&mut CharacterControl, &mut AvoidWallsData)>,
+    mut actor_query: Query<(&AlienBehavior, &mut CharacterControl, &mut AvoidWallsData)>,
 ) {
-    for (mut controller, mut avoid_walls_data) in actor_query.iter_mut() {
+    for (behavior, mut controller, mut avoid_walls_data) in actor_query.iter_mut() {
+        if !behavior.state.avoids_walls() {
+            continue;
+        }
         if avoid_walls_data.forward_distance >= avoid_walls_data.max_forward_distance {
-            continue; // Not blocking, skip
+            // Not blocking; undo any slow-down from last frame.
+            controller.speed = controller.max_speed;
+            continue;
         }
         if avoid_walls_data.left_distance < avoid_walls_data.max_left_distance {
             avoid_walls_data.rotation_direction = ControlRotation::Right;
@@ -1,93 +1,137 @@
+use std::collections::{HashMap, HashSet};
 use bevy::prelude::*;
 use avian3d::prelude::{Position, Rotation};
+use crate::ai::components::alien_behavior_components::{AlienBehavior, AlienBehaviorState};
 use crate::ai::components::move_towards_goal_components::AgentCannotFindPath;
 use crate::alien::components::general::Alien;
-use crate::general::components::map_components::CurrentTile;
+use crate::general::components::map_components::{CoolDown, CurrentTile, Terrain};
 use crate::general::resources::map_resources::MapGraph;
 use crate::player::components::IsObstacle;
-use pathfinding::directed::astar::astar;
-use crate::ai::components::destroy_the_map_components::{MustDestroyTheMap, MustDestroyTheMapState};
+use crate::ai::components::destroy_the_map_components::{MustDestroyTheMap, MustDestroyTheMapState, Wrecker};
 use crate::general::systems::map_systems::TileDefinitions;
 use itertools::Itertools;
 use crate::building::systems::ToWorldCoordinates;
 use crate::control::components::{ControlDirection, CharacterControl, ControlRotation};
 use crate::general::components::Health;
+use crate::player::events::building_events::AddTile;
+use crate::general::events::map_events::GridChanged;
+use crate::map::flow_field::FlowField;
+use crate::status_effects::components::StatusEffects;
+use crate::towers::components::Trap;
 
 pub fn agent_cant_find_path(
     mut alien_cant_find_path_mr: MessageReader<AgentCannotFindPath>,
+    wrecker_query: Query<&Wrecker>,
     mut commands: Commands,
 ) {
     for AgentCannotFindPath(alien) in alien_cant_find_path_mr.read() {
-        if let Ok(mut alien_commands) = commands.get_entity(*alien) {
-            alien_commands.insert(MustDestroyTheMap::new());
+        if let Ok(wrecker) = wrecker_query.get(*alien)
+            && let Ok(mut alien_commands) = commands.get_entity(*alien) {
+            alien_commands.insert(MustDestroyTheMap::new(wrecker.damage));
         }
     }
 }
 
+/// Aliens walking the `FlowField` pick up grid changes on their own; destroyers follow a
+/// cached `path_of_destruction`. Release the ones whose goal became reachable again and
+/// send the ones whose route or target was touched back to searching.
+pub fn replan_on_grid_change(
+    mut grid_changed_mr: MessageReader<GridChanged>,
+    flow_field: Res<FlowField>,
+    mut alien_query: Query<(&AlienBehavior, &mut MustDestroyTheMap, &mut CharacterControl, &CurrentTile), With<Alien>>,
+) {
+    let changed: HashSet<(usize, usize)> = grid_changed_mr.read()
+        .flat_map(|grid_changed| grid_changed.tiles.iter().copied())
+        .collect();
+    if changed.is_empty() {
+        return;
+    }
+
+    for (behavior, mut must_destroy_data, mut controller, alien_current_tile) in alien_query.iter_mut() {
+        let route_opened = flow_field.reaches_goal(alien_current_tile.tile);
+        let path_touched = must_destroy_data.target_tile.is_some_and(|t| changed.contains(&t))
+            || must_destroy_data.path_of_destruction.as_ref()
+                .is_some_and(|path| path.iter().any(|t| changed.contains(t)));
+        if !route_opened && !path_touched {
+            continue;
+        }
+
+        must_destroy_data.path_of_destruction = None;
+        must_destroy_data.target_tile = None;
+        must_destroy_data.state = if route_opened {
+            MustDestroyTheMapState::Finished
+        } else {
+            MustDestroyTheMapState::SearchingForThingToDestroy
+        };
+        if behavior.is(AlienBehaviorState::Breach) {
+            controller.rotations.clear();
+            controller.directions.clear();
+        }
+    }
+}
+
+/// Drives aliens that can't reach the goal through breaking a way in. A search runs one A*
+/// to the goal over floor and obstacles alike, weighted by obstacle health, and the first
+/// obstacle on that path becomes the target. The alien keeps its claim on the target,
+/// hitting it `attack_rate_per_minute` times a minute, until it falls.
 pub fn destroy_the_map_action_system(
     mut commands: Commands,
     mut map_graph: ResMut<MapGraph>,
-    mut alien_query: Query<(Entity, &mut MustDestroyTheMap, &mut CharacterControl, &Position, &Rotation, &CurrentTile), With<Alien>>,
-    mut obstacle_query: Query<(&IsObstacle, &CurrentTile, &mut Health)>,
+    mut alien_query: Query<(Entity, &AlienBehavior, &mut MustDestroyTheMap, &mut CharacterControl, &Position, &Rotation, &CurrentTile, Option<&StatusEffects>), With<Alien>>,
+    // Traps never leave the path, so there is no reason to break one.
+    mut obstacle_query: Query<(&IsObstacle, &CurrentTile, &mut Health), Without<Trap>>,
     tile_definitions: Res<TileDefinitions>,
+    mut add_tile_mw: MessageWriter<AddTile>,
+    time: Res<Time>,
 ) {
+    let mut claimed: HashMap<(usize, usize), usize> = alien_query
+        .iter()
+        .filter_map(|(_, _, must_destroy_data, ..)| must_destroy_data.target_tile)
+        .counts();
+
     for (entity,
+         behavior,
          mut must_destroy_data,
          mut controller,
          alien_position,
          alien_rotation,
          alien_current_tile,
+         status_effects,
     ) in alien_query.iter_mut() {
+        // Chasing, fleeing or a stun puts the breach on hold; it picks up where it left off.
+        if !behavior.is(AlienBehaviorState::Breach) || status_effects.is_some_and(StatusEffects::stunned) {
+            continue;
+        }
         match must_destroy_data.state {
             MustDestroyTheMapState::NotStarted => {
                 must_destroy_data.state = MustDestroyTheMapState::SearchingForThingToDestroy;
             }
             MustDestroyTheMapState::SearchingForThingToDestroy => {
-                let mut potential_targets: Vec<(usize, usize)> =
-                    obstacle_query
-                        .iter()
-                        .map(|(_, current_tile, _)| current_tile.tile)
-                        .sorted_by(|a, b|
-                            map_graph.path_finding_grid.distance(*b, alien_current_tile.tile)
-                                .cmp(&map_graph.path_finding_grid.distance(*a, alien_current_tile.tile)))
-                        .collect();
-
-                if potential_targets.is_empty() {
-                    must_destroy_data.state = MustDestroyTheMapState::Failed;
-                    continue;
-                }
-
-                let mut try_this_one = potential_targets.pop().unwrap();
-                map_graph.path_finding_grid.add_vertex(try_this_one);
-                let mut need_path = true;
+                // Obstacles others are already breaking count for less: they will fall sooner,
+                // so aliens stuck behind the same wall gather on one target.
+                let obstacles: HashMap<(usize, usize), i32> = obstacle_query
+                    .iter()
+                    .map(|(_, current_tile, health)| {
+                        let attackers = claimed.get(&current_tile.tile).copied().unwrap_or(0) as i32;
+                        (current_tile.tile, health.health / (attackers + 1))
+                    })
+                    .collect();
 
-                while need_path {
-                    match astar(
-                        &alien_current_tile.tile,
-                        |t| map_graph.path_finding_grid.neighbours(*t).into_iter().map(|t| (t, 1)),
-                        |t| map_graph.path_finding_grid.distance(*t, try_this_one),
-                        |t| *t == try_this_one) {
-                        None => {
-                            match potential_targets.pop() {
-                                None => {
-                                    map_graph.path_finding_grid.remove_vertex(try_this_one);
-                                    must_destroy_data.state = MustDestroyTheMapState::Failed;
-                                    need_path = false;
-                                }
-                                Some(target) => {
-                                    map_graph.path_finding_grid.remove_vertex(try_this_one);
-                                    try_this_one = target;
-                                    map_graph.path_finding_grid.add_vertex(try_this_one);
-                                }
-                            }
-                        }
-                        Some(path) => {
-                            must_destroy_data.target_tile = Some(try_this_one);
-                            map_graph.path_finding_grid.remove_vertex(try_this_one);
-                            must_destroy_data.path_of_destruction = Some(path.0[1..].to_vec());
-                            need_path = false;
-                            must_destroy_data.state = MustDestroyTheMapState::MovingTowardsThingToDestroy;
-                        }
+                let target = map_graph
+                    .find_breach_path(alien_current_tile.tile, map_graph.goal, &obstacles)
+                    .and_then(|(path, _)| {
+                        let index = path.iter().position(|tile| obstacles.contains_key(tile))?;
+                        Some((path[index], path[1..=index].to_vec()))
+                    });
+                match target {
+                    None => {
+                        must_destroy_data.state = MustDestroyTheMapState::Failed;
+                    }
+                    Some((target_tile, path)) => {
+                        must_destroy_data.target_tile = Some(target_tile);
+                        must_destroy_data.path_of_destruction = Some(path);
+                        must_destroy_data.state = MustDestroyTheMapState::MovingTowardsThingToDestroy;
+                        *claimed.entry(target_tile).or_default() += 1;
                     }
                 }
             }
@@ -123,6 +167,7 @@ pub fn destroy_the_map_action_system(
                                             let angle = alien_direction_vector2.angle_to(alien_to_goal_direction).to_degrees();
                                             controller.rotations.clear();
                                             controller.directions.clear();
+                                            controller.speed = controller.max_speed * map_graph.terrain_at(alien_current_tile.tile).speed_factor();
                                             let angle_speed_value = 90.0;
                                             let angle_forward_value = 15.0;
                                             if angle.abs() < angle_speed_value {
@@ -159,23 +204,29 @@ pub fn destroy_the_map_action_system(
                     }
                     Some(target_tile) => {
                         let target_tile = *target_tile;
-                        let mut did_not_hit = true;
-                        for (_, tower_tile, mut health) in obstacle_query.iter_mut() {
-                            if tower_tile.tile == target_tile {
-                                did_not_hit = false;
-                                health.health -= 10;
+                        controller.rotations.clear();
+                        controller.directions.clear();
+                        if !must_destroy_data.cool_down(time.delta_secs()) {
+                            continue;
+                        }
+                        // Keep the claim between hits so others still weigh this target as shared.
+                        match obstacle_query.iter_mut().find(|(_, tile, _)| tile.tile == target_tile) {
+                            Some((_, _, mut health)) => {
+                                health.health -= must_destroy_data.damage;
                                 if health.health <= 0 {
-                                    map_graph.path_finding_grid.add_vertex(target_tile);
+                                    // The wreckage slows whoever walks over it next.
+                                    map_graph.set_terrain(target_tile, Terrain::Rubble);
+                                    add_tile_mw.write(AddTile(target_tile));
+                                    must_destroy_data.target_tile = None;
+                                    must_destroy_data.state = MustDestroyTheMapState::Finished;
                                 }
+                            }
+                            None => {
                                 must_destroy_data.target_tile = None;
-                                must_destroy_data.state = MustDestroyTheMapState::Finished;
-                                break;
+                                must_destroy_data.state = MustDestroyTheMapState::Failed;
+                                must_destroy_data.path_of_destruction = None;
                             }
                         }
-                        if did_not_hit {
-                            must_destroy_data.state = MustDestroyTheMapState::Failed;
-                            must_destroy_data.path_of_destruction = None;
-                        }
                     }
                 }
             }
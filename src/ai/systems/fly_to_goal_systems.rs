@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+use avian3d::prelude::{LinearVelocity, Position, Rotation};
+use crate::ai::components::move_towards_goal_components::AgentReachedGoal;
+use crate::alien::components::general::Flyer;
+use crate::building::systems::ToWorldCoordinates;
+use crate::general::components::map_components::AlienGoal;
+use crate::general::resources::map_resources::MapGraph;
+use crate::general::systems::map_systems::TileDefinitions;
+use crate::status_effects::components::StatusEffects;
+
+/// How close over the goal tile counts as arrived.
+const FLYER_GOAL_DISTANCE: f32 = 0.5;
+
+/// Flies every flyer straight at the goal, climbing or sinking to its altitude on the way.
+/// Walls and obstacles don't matter; it hands over to `agent_reached_goal_handler` on arrival.
+pub fn fly_to_goal_system(
+    map_graph: Res<MapGraph>,
+    tile_definitions: Res<TileDefinitions>,
+    mut flyer_query: Query<(Entity, &Flyer, &Position, &mut LinearVelocity, &mut Rotation, Option<&StatusEffects>)>,
+    mut alien_reached_goal_mw: MessageWriter<AgentReachedGoal>,
+    goal_query: Query<(), With<AlienGoal>>,
+) {
+    if goal_query.is_empty() {
+        return;
+    }
+    let goal = map_graph.goal.to_world_coords(&tile_definitions).xz();
+    // Spawn points and the goal sit at this height; altitude is counted from it.
+    let ground = -tile_definitions.wall_height;
+
+    for (entity, flyer, position, mut velocity, mut rotation, status_effects) in flyer_query.iter_mut() {
+        let to_goal = goal - position.0.xz();
+        if to_goal.length() < FLYER_GOAL_DISTANCE {
+            alien_reached_goal_mw.write(AgentReachedGoal(entity));
+            continue;
+        }
+        let speed = flyer.speed * status_effects.map_or(1.0, |effects| effects.speed_multiplier());
+        let horizontal = to_goal.normalize() * speed;
+        let climb = (ground + flyer.altitude - position.0.y).clamp(-speed, speed);
+        velocity.0 = Vec3::new(horizontal.x, climb, horizontal.y);
+        // Face along the flight, -Z being forward.
+        rotation.0 = Quat::from_rotation_y((-horizontal.x).atan2(-horizontal.y));
+    }
+}
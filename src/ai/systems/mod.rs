@@ -3,3 +3,7 @@ pub(crate) mod avoid_walls_systems;
 pub(crate) mod move_forward_systems;
 pub(crate) mod move_towards_goal_systems;
 pub(crate) mod destroy_the_map_systems;
+pub(crate) mod alien_behavior_systems;
+pub(crate) mod senses_systems;
+pub(crate) mod ranged_attack_systems;
+pub(crate) mod fly_to_goal_systems;
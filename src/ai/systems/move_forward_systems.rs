@@ -1,12 +1,16 @@
 use bevy::prelude::{Query, With};
+use crate::ai::components::alien_behavior_components::{AlienBehavior, AlienBehaviorState};
 use crate::alien::components::general::Alien;
 use crate::control::components::{ControlDirection, CharacterControl};
 
-#[allow(dead_code)]
+/// Wandering: straight ahead at full speed until something better comes up.
 pub fn move_forward_system(
-    mut controller_query: Query<&mut CharacterControl, With<Alien>>,
+    mut controller_query: Query<(&AlienBehavior, &mut CharacterControl), With<Alien>>,
 ) {
-    for mut controller in controller_query.iter_mut() {
+    for (behavior, mut controller) in controller_query.iter_mut() {
+        if !behavior.is(AlienBehaviorState::Wander) {
+            continue;
+        }
         controller.rotations.clear();
         controller.speed = controller.max_speed;
         controller.directions.insert(ControlDirection::Forward);
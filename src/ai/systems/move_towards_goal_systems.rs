@@ -1,10 +1,12 @@
 use bevy::prelude::*;
-use avian3d::prelude::{LinearVelocity, Position, Rotation};
+use avian3d::prelude::{Position, Rotation};
+use crate::ai::components::alien_behavior_components::{AlienBehavior, AlienBehaviorState};
+use crate::ai::components::destroy_the_map_components::MustDestroyTheMap;
 use crate::ai::components::move_towards_goal_components::{AgentCannotFindPath, AgentReachedGoal, MoveTowardsGoalData};
 use crate::alien::components::general::{Alien, AlienCounter};
 use crate::general::components::map_components::{AlienGoal, CurrentTile};
 use crate::general::resources::map_resources::MapGraph;
-use pathfinding::directed::astar::astar;
+use crate::map::flow_field::FlowField;
 use crate::building::systems::ToWorldCoordinates;
 use crate::control::components::{ControlDirection, CharacterControl, ControlRotation};
 use crate::game_state::score_keeper::GameTrackingEvent;
@@ -12,7 +14,8 @@ use crate::general::systems::map_systems::TileDefinitions;
 
 pub fn move_towards_goal_system(
     map_graph: Res<MapGraph>,
-    mut alien_query: Query<(Entity, &mut MoveTowardsGoalData, &mut CharacterControl, &Position, &Rotation, &CurrentTile, &LinearVelocity), With<Alien>>,
+    flow_field: Res<FlowField>,
+    mut alien_query: Query<(Entity, &AlienBehavior, &mut CharacterControl, &Position, &Rotation, &CurrentTile), (With<Alien>, With<MoveTowardsGoalData>, Without<MustDestroyTheMap>)>,
     mut alien_reached_goal_mw: MessageWriter<AgentReachedGoal>,
     mut cant_find_path_mw: MessageWriter<AgentCannotFindPath>,
     tile_definitions: Res<TileDefinitions>,
@@ -23,78 +26,53 @@ pub fn move_towards_goal_system(
     }
 
     for (entity,
-         mut move_towards_goal_data,
+         behavior,
          mut controller,
          alien_position,
          alien_rotation,
          alien_current_tile,
-         linear_velocity,
     ) in alien_query.iter_mut() {
-        if linear_velocity.0.length() < 0.001 {
-            move_towards_goal_data.path = None;
+        let tile = alien_current_tile.tile;
+        if tile == flow_field.goal {
+            alien_reached_goal_mw.write(AgentReachedGoal(entity));
             continue;
         }
+        if !flow_field.reaches_goal(tile) {
+            cant_find_path_mw.write(AgentCannotFindPath(entity));
+            continue;
+        }
+        // Goal and path checks above run whatever the alien is doing; steering only while seeking.
+        if !behavior.is(AlienBehaviorState::SeekGoal) {
+            continue;
+        }
+        let Some(next_tile) = flow_field.next_tile(tile) else { continue };
 
-        match &move_towards_goal_data.path.clone() {
-            None => {
-                let astar_result = astar(
-                    &alien_current_tile.tile,
-                    |t| map_graph.path_finding_grid.neighbours(*t).into_iter().map(|t| (t, 1)),
-                    |t| map_graph.path_finding_grid.distance(*t, map_graph.goal),
-                    |t| *t == map_graph.goal,
-                );
-                match astar_result {
-                    None => {
-                        cant_find_path_mw.write(AgentCannotFindPath(entity));
-                    }
-                    Some(path) => {
-                        move_towards_goal_data.path = Some(path.0[1..].to_vec());
-                    }
-                }
-            }
-            Some(path) => {
-                if path.is_empty() {
-                    move_towards_goal_data.path = None;
-                    alien_reached_goal_mw.write(AgentReachedGoal(entity));
-                } else {
-                    let next_tile = path[0];
-                    if map_graph.path_finding_grid.has_vertex(next_tile) {
-                        let next_tile_position = next_tile.to_world_coords(&tile_definitions).xz();
-                        let alien_position_vector2 = alien_position.0.xz();
-                        let alien_direction_vector2 = alien_rotation.0.mul_vec3(Vec3::new(0.0, 0.0, -1.0)).xz();
-                        let alien_to_goal_direction = next_tile_position - alien_position_vector2;
-                        let distance = alien_to_goal_direction.length();
-                        if distance < 0.5 {
-                            move_towards_goal_data.path = Some(path[1..].to_vec());
-                            continue;
-                        }
+        let next_tile_position = next_tile.to_world_coords(&tile_definitions).xz();
+        let alien_position_vector2 = alien_position.0.xz();
+        let alien_direction_vector2 = alien_rotation.0.mul_vec3(Vec3::new(0.0, 0.0, -1.0)).xz();
+        let alien_to_goal_direction = next_tile_position - alien_position_vector2;
 
-                        let angle = alien_direction_vector2.angle_to(alien_to_goal_direction).to_degrees();
-                        controller.rotations.clear();
-                        controller.directions.clear();
-                        let angle_speed_value = 90.0;
-                        let angle_forward_value = 15.0;
-                        if angle.abs() < angle_speed_value {
-                            controller.turn_speed = controller.max_turn_speed * (angle.abs() / angle_speed_value);
-                        } else {
-                            controller.turn_speed = controller.max_turn_speed;
-                        }
-                        if angle.abs() > 1.0 {
-                            if angle > 0.0 {
-                                controller.rotations.insert(ControlRotation::Right);
-                            } else {
-                                controller.rotations.insert(ControlRotation::Left);
-                            }
-                        }
-                        if angle.abs() < angle_forward_value {
-                            controller.directions.insert(ControlDirection::Forward);
-                        }
-                    } else {
-                        move_towards_goal_data.path = None;
-                    }
-                }
+        let angle = alien_direction_vector2.angle_to(alien_to_goal_direction).to_degrees();
+        controller.rotations.clear();
+        controller.directions.clear();
+        controller.speed = controller.max_speed * map_graph.terrain_at(tile).speed_factor();
+        let angle_speed_value = 90.0;
+        let angle_forward_value = 15.0;
+        if angle.abs() < angle_speed_value {
+            controller.turn_speed = controller.max_turn_speed * (angle.abs() / angle_speed_value);
+        } else {
+            controller.turn_speed = controller.max_turn_speed;
+        }
+        if angle.abs() > 1.0 {
+            if angle > 0.0 {
+                controller.rotations.insert(ControlRotation::Right);
+            } else {
+                controller.rotations.insert(ControlRotation::Left);
             }
         }
+        if angle.abs() < angle_forward_value {
+            controller.directions.insert(ControlDirection::Forward);
+        }
     }
 }
 
@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+use avian3d::prelude::{Position, Rotation};
+use crate::ai::components::alien_behavior_components::{AlienBehavior, AlienBehaviorState};
+use crate::ai::components::approach_and_attack_player_components::ApproachAndAttackPlayerData;
+use crate::ai::components::destroy_the_map_components::MustDestroyTheMap;
+use crate::ai::components::ranged_attack_components::RangedAttack;
+use crate::alien::components::general::Alien;
+use crate::control::components::{CharacterControl, ControlRotation};
+use crate::general::components::map_components::CurrentTile;
+use crate::player::components::{IsObstacle, Player};
+use crate::projectiles::events::FireProjectile;
+use crate::status_effects::components::StatusEffects;
+use crate::towers::components::Trap;
+
+/// Points every ranged alien at the player it sees, or failing that the obstacle it is
+/// breaching, whichever is within range.
+pub fn ranged_target_system(
+    mut ranged_query: Query<(&mut RangedAttack, &Position, Option<&ApproachAndAttackPlayerData>, Option<&MustDestroyTheMap>), With<Alien>>,
+    player_query: Query<&Position, With<Player>>,
+    obstacle_query: Query<(Entity, &Position, &CurrentTile), (With<IsObstacle>, Without<Trap>)>,
+) {
+    for (mut ranged, position, approach_player_data, must_destroy_data) in ranged_query.iter_mut() {
+        let player = approach_player_data
+            .and_then(|data| data.seen_player)
+            .and_then(|player| player_query.get(player).ok().map(|p| (player, p.0)));
+        let obstacle = must_destroy_data
+            .and_then(|data| data.target_tile)
+            .and_then(|tile| obstacle_query.iter().find(|(_, _, current_tile)| current_tile.tile == tile))
+            .map(|(entity, p, _)| (entity, p.0));
+        let in_range = player.into_iter()
+            .chain(obstacle)
+            .find(|(_, target)| target.distance(position.0) <= ranged.range);
+        ranged.target = in_range.map(|(entity, _)| entity);
+        ranged.aim_point = in_range.map(|(_, target)| target);
+    }
+}
+
+/// Stands still, turns to the target and shoots once roughly facing it. Reloads all the
+/// while, so turning doesn't hold back the next shot.
+pub fn spit_system(
+    time: Res<Time>,
+    mut alien_query: Query<(Entity, &AlienBehavior, &mut RangedAttack, &mut CharacterControl, &Position, &Rotation, Option<&StatusEffects>), With<Alien>>,
+    mut fire_projectile_mw: MessageWriter<FireProjectile>,
+) {
+    for (entity, behavior, mut ranged, mut controller, position, rotation, status_effects) in alien_query.iter_mut() {
+        ranged.reload(time.delta_secs());
+        if !behavior.is(AlienBehaviorState::Spit) || status_effects.is_some_and(StatusEffects::stunned) {
+            continue;
+        }
+        let Some(aim_point) = ranged.aim_point else { continue };
+        let alien_to_target = aim_point.xz() - position.0.xz();
+        let alien_direction = rotation.0.mul_vec3(Vec3::new(0.0, 0.0, -1.0)).xz();
+        let angle = alien_direction.angle_to(alien_to_target).to_degrees();
+
+        controller.rotations.clear();
+        controller.directions.clear();
+        if angle.abs() > 5.0 {
+            controller.rotations.insert(if angle > 0.0 { ControlRotation::Right } else { ControlRotation::Left });
+        }
+        if angle.abs() < 20.0 && ranged.fire() {
+            let direction = (aim_point - position.0).normalize_or_zero();
+            fire_projectile_mw.write(FireProjectile {
+                projectile: ranged.projectile.clone(),
+                shooter: entity,
+                origin: position.0 + direction * 0.3 + Vec3::new(0.0, 0.25, 0.0),
+                direction,
+                target: ranged.target,
+                aim_point: Some(aim_point),
+                damage: Some(ranged.damage),
+                hostile: true,
+                anti_air: false,
+            });
+        }
+    }
+}
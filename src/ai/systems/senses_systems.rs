@@ -0,0 +1,20 @@
+use bevy::prelude::{MessageReader, Query, With};
+use avian3d::prelude::Position;
+use crate::ai::components::approach_and_attack_player_components::ApproachAndAttackPlayerData;
+use crate::ai::components::senses_components::Noise;
+use crate::alien::components::general::Alien;
+
+/// Aliens within earshot of a noise remember where it came from, unless a player is
+/// already in sight. The newest noise wins.
+pub fn hear_noise_system(
+    mut noise_mr: MessageReader<Noise>,
+    mut alien_query: Query<(&mut ApproachAndAttackPlayerData, &Position), With<Alien>>,
+) {
+    for noise in noise_mr.read() {
+        for (mut alien_brain, position) in alien_query.iter_mut() {
+            if alien_brain.seen_player.is_none() && position.0.distance(noise.position) <= noise.radius {
+                alien_brain.remember(noise.position);
+            }
+        }
+    }
+}
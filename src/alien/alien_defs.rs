@@ -0,0 +1,232 @@
+use std::path::Path;
+use bevy::math::{EulerRot, Quat, Vec3};
+use bevy::log::warn;
+use bevy::prelude::Resource;
+use serde::Deserialize;
+use crate::economy::resources::KILL_REWARD;
+use crate::general::ron_dir::load_ron_dir;
+use crate::map::campaign::Campaign;
+use crate::player::systems::spawn_players::FixSceneTransform;
+
+pub const ALIENS_DIR: &str = "assets/aliens";
+
+/// The alien spawned when none is named: the built-in spider unless `assets/aliens/spider.ron` overrides it.
+pub fn default_alien() -> String { "spider".to_string() }
+fn default_model() -> String { "quaternius/alien.glb#Scene0".to_string() }
+fn default_animations() -> String { "aliens".to_string() }
+fn default_scale() -> f32 { 0.25 }
+fn default_speed() -> f32 { 1.0 }
+fn default_turn_speed() -> f32 { 3.0 }
+fn default_collider() -> (f32, f32) { (1.0, 1.0) }
+fn default_health() -> i32 { 100 }
+fn default_attack() -> i32 { 5 }
+fn default_bounty() -> u32 { KILL_REWARD }
+fn default_wreck_damage() -> i32 { 10 }
+
+/// Corrects a model's own offset, facing and size inside the alien's body.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct ModelFix {
+    pub translation: (f32, f32, f32),
+    /// Turn around the vertical axis, in degrees.
+    pub yaw: f32,
+    pub scale: f32,
+}
+
+impl Default for ModelFix {
+    fn default() -> Self {
+        Self {
+            translation: (0.0, -0.35, 0.0),
+            yaw: 180.0,
+            scale: 0.5,
+        }
+    }
+}
+
+impl ModelFix {
+    pub fn fix_scene_transform(&self) -> FixSceneTransform {
+        FixSceneTransform::new(
+            Vec3::new(self.translation.0, self.translation.1, self.translation.2),
+            Quat::from_euler(EulerRot::YXZ, self.yaw.to_radians(), 0.0, 0.0),
+            Vec3::splat(self.scale),
+        )
+    }
+}
+
+/// The cone an alien spots players with, pointing the way it faces.
+#[derive(Deserialize, Clone, Copy, Debug)]
+#[serde(default)]
+pub struct AlienSight {
+    pub radius: f32,
+    pub height: f32,
+    /// How far the cone is swept ahead.
+    pub range: f32,
+}
+
+impl Default for AlienSight {
+    fn default() -> Self {
+        Self {
+            radius: 5.0,
+            height: 4.0,
+            range: 5.0,
+        }
+    }
+}
+
+/// Which AI behaviours an alien gets. All on unless turned off.
+#[derive(Deserialize, Clone, Copy, Debug)]
+#[serde(default)]
+pub struct AlienBehaviours {
+    /// Walks the flow field toward the goal.
+    pub seek_goal: bool,
+    /// Turns on players it sees and attacks them.
+    pub chase_player: bool,
+    /// Breaks obstacles when the goal can't be reached, rather than staying stuck.
+    pub destroy_map: bool,
+}
+
+impl Default for AlienBehaviours {
+    fn default() -> Self {
+        Self {
+            seek_goal: true,
+            chase_player: true,
+            destroy_map: true,
+        }
+    }
+}
+
+/// How an alien gets around.
+#[derive(Deserialize, Clone, Copy, Debug, Default)]
+pub enum AlienMovement {
+    /// A physics capsule on the tile grid, steered by the AI behaviours.
+    #[default]
+    Walk,
+    /// Straight to the goal at `altitude` above the floor, ignoring the grid. Only anti-air
+    /// towers can hit it, and walking-only behaviours, traps and auras leave it alone.
+    Fly { altitude: f32 },
+}
+
+/// Stops once a player or the obstacle it is breaching is within `range`, and shoots at it.
+#[derive(Deserialize, Clone, Debug)]
+pub struct AlienRanged {
+    pub range: f32,
+    pub fire_rate_per_minute: f32,
+    /// Key of a `ProjectileDef` in `assets/projectiles/`.
+    pub projectile: String,
+    pub damage: i32,
+}
+
+/// One kind of alien, read from `assets/aliens/<key>.ron`.
+#[derive(Deserialize, Clone, Debug)]
+pub struct AlienDef {
+    /// File stem; filled in by `AlienDefs::load`.
+    #[serde(skip)]
+    pub key: String,
+    /// Scene path relative to `assets/`.
+    #[serde(default = "default_model")]
+    pub model: String,
+    /// Group in the `AnimationStore` the model's clips live in.
+    #[serde(default = "default_animations")]
+    pub animations: String,
+    /// Uniform scale of the whole alien, collider included.
+    #[serde(default = "default_scale")]
+    pub scale: f32,
+    #[serde(default)]
+    pub model_fix: ModelFix,
+    #[serde(default = "default_speed")]
+    pub speed: f32,
+    #[serde(default = "default_turn_speed")]
+    pub turn_speed: f32,
+    /// Capsule collider as `(radius, length)`, before `scale`.
+    #[serde(default = "default_collider")]
+    pub collider: (f32, f32),
+    #[serde(default = "default_health")]
+    pub health: i32,
+    /// Damage per hit on a player.
+    #[serde(default = "default_attack")]
+    pub attack: i32,
+    /// Damage per hit on an obstacle while destroying the map.
+    #[serde(default = "default_wreck_damage")]
+    pub wreck_damage: i32,
+    #[serde(default)]
+    pub sight: AlienSight,
+    #[serde(default)]
+    pub behaviours: AlienBehaviours,
+    #[serde(default)]
+    pub movement: AlienMovement,
+    #[serde(default)]
+    pub ranged: Option<AlienRanged>,
+    /// Share of its health below which it runs from nearby players; 0 never runs.
+    #[serde(default)]
+    pub flee_below: f32,
+    /// Credits paid for killing one.
+    #[serde(default = "default_bounty")]
+    pub bounty: u32,
+}
+
+/// Every alien kind, by key.
+#[derive(Resource, Debug)]
+pub struct AlienDefs {
+    pub defs: Vec<AlienDef>,
+}
+
+impl AlienDefs {
+    /// Reads every `*.ron` in `ALIENS_DIR`, always keeping a built-in `spider` so levels
+    /// without a type, or with a misspelt one, still spawn something.
+    pub fn load() -> Self {
+        let mut defs: Vec<AlienDef> = load_ron_dir(Path::new(ALIENS_DIR))
+            .into_iter()
+            .map(|(key, def)| AlienDef { key, ..def })
+            .collect();
+        if !defs.iter().any(|def| def.key == default_alien()) {
+            defs.push(Self::spider());
+        }
+        Self { defs }
+    }
+
+    fn spider() -> AlienDef {
+        AlienDef {
+            key: default_alien(),
+            model: default_model(),
+            animations: default_animations(),
+            scale: default_scale(),
+            model_fix: ModelFix::default(),
+            speed: default_speed(),
+            turn_speed: default_turn_speed(),
+            collider: default_collider(),
+            health: default_health(),
+            attack: default_attack(),
+            wreck_damage: default_wreck_damage(),
+            sight: AlienSight::default(),
+            behaviours: AlienBehaviours::default(),
+            movement: AlienMovement::Walk,
+            ranged: None,
+            flee_below: 0.0,
+            bounty: default_bounty(),
+        }
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.defs.iter().any(|def| def.key == key)
+    }
+
+    /// Warns about every wave group in `campaign` whose alien has no definition. They spawn spiders.
+    pub fn check_campaign(&self, campaign: &Campaign) {
+        for level in &campaign.levels {
+            for (wave, group) in level.waves.iter().enumerate()
+                .flat_map(|(index, wave)| wave.groups.iter().map(move |group| (index + 1, group))) {
+                if !self.contains(&group.alien) {
+                    warn!("{}: wave {wave} has unknown alien {:?}, spawning spiders", level.name, group.alien);
+                }
+            }
+        }
+    }
+
+    /// The definition for `key`, or the spider if there is none.
+    pub fn get(&self, key: &str) -> &AlienDef {
+        self.defs.iter()
+            .find(|def| def.key == key)
+            .or_else(|| self.defs.iter().find(|def| def.key == default_alien()))
+            .expect("AlienDefs always holds a spider")
+    }
+}
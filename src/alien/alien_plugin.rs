@@ -1,6 +1,8 @@
-use bevy::app::{App, Plugin, Update};
+use bevy::app::{App, Plugin, Startup, Update};
 use bevy::prelude::{in_state, IntoScheduleConfigs};
+use crate::alien::alien_defs::AlienDefs;
 use crate::alien::systems::spawn_aliens::{alien_spawner_system, spawn_aliens};
+use crate::alien::systems::waves::{call_next_wave_system, check_wave_aliens, wave_system};
 use crate::game_state::GameState;
 
 #[allow(dead_code)]
@@ -9,6 +11,7 @@ pub struct AlienPlugin;
 impl Plugin for AlienPlugin {
     fn build(&self, app: &mut App) {
         app
+            .insert_resource(AlienDefs::load())
             .add_systems(
                 Update,
                 (
@@ -24,12 +27,16 @@ pub struct StatefulAlienPlugin;
 impl Plugin for StatefulAlienPlugin {
     fn build(&self, app: &mut App) {
         app
+            .insert_resource(AlienDefs::load())
+            .add_systems(Startup, check_wave_aliens)
             .add_systems(
                 Update,
                 (
                     alien_spawner_system,
+                    call_next_wave_system,
+                    wave_system,
                     spawn_aliens,
-                ).run_if(in_state(GameState::InGame)),
+                ).chain().run_if(in_state(GameState::InGame)),
             );
     }
 }
@@ -13,16 +13,37 @@ pub struct AlienSightShape {
     pub range: f32,
 }
 
-impl Default for AlienSightShape {
-    fn default() -> Self {
+impl AlienSightShape {
+    pub fn new(radius: f32, height: f32, range: f32) -> Self {
         AlienSightShape {
-            shape: Collider::cone(5.0, 4.0),
+            shape: Collider::cone(radius, height),
             rotation: Quat::from_euler(EulerRot::YXZ, 0.0, -90.0, 0.0),
-            range: 5.0,
+            range,
         }
     }
 }
 
+impl Default for AlienSightShape {
+    fn default() -> Self {
+        AlienSightShape::new(5.0, 4.0, 5.0)
+    }
+}
+
+/// Flies straight for the goal at `altitude`, over walls and obstacles alike.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Flyer {
+    pub altitude: f32,
+    pub speed: f32,
+}
+
+/// The wave, counted from 1, an alien was sent in. Its wave's reward waits until it is gone.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct FromWave(pub usize);
+
+/// Credits paid out when this alien is killed.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Bounty(pub u32);
+
 #[derive(Resource, Clone, Debug)]
 pub struct AlienCounter {
     pub count: u32,
@@ -1,3 +1,4 @@
 pub(crate) mod systems;
 pub(crate) mod components;
 pub mod alien_plugin;
+pub(crate) mod alien_defs;
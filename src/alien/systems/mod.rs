@@ -1 +1,2 @@
 pub(crate) mod spawn_aliens;
+pub(crate) mod waves;
@@ -1,32 +1,43 @@
-use bevy::math::{EulerRot, Quat, Vec3};
+use bevy::asset::AssetServer;
+use bevy::math::{Quat, Vec3};
 use bevy::prelude::{Commands, MessageReader, MessageWriter, Name, Query, Res, ResMut, Time, Transform};
 use bevy::scene::SceneRoot;
 use avian3d::prelude::{AngularDamping, Collider, CollisionLayers, Friction, LinearDamping, LockedAxes, Position, RigidBody};
 use std::f32::consts::PI;
 use bevy_wind_waker_shader::WindWakerShaderBuilder;
+use crate::ai::components::alien_behavior_components::AlienBehavior;
 use crate::ai::components::approach_and_attack_player_components::ApproachAndAttackPlayerData;
 use crate::ai::components::avoid_wall_components::AvoidWallsData;
+use crate::ai::components::destroy_the_map_components::Wrecker;
 use crate::ai::components::move_towards_goal_components::MoveTowardsGoalData;
-use crate::alien::components::general::{Alien, AlienCounter, AlienSightShape};
+use crate::ai::components::ranged_attack_components::RangedAttack;
+use crate::alien::alien_defs::{default_alien, AlienDefs, AlienMovement};
+use crate::alien::components::general::{Alien, AlienCounter, AlienSightShape, Bounty, Flyer, FromWave};
 use crate::animation::animation_plugin::{AnimationKey, CurrentAnimationKey};
-use crate::assets::assets_plugin::GameAssets;
 use crate::control::components::{CharacterControl, DynamicMovement};
-use crate::game_state::score_keeper::GameTrackingEvent;
+use crate::game_state::score_keeper::{GameTrackingEvent, LevelTracker};
 use crate::general::components::{Attack, CollisionLayer, Health, HittableTarget};
 use crate::general::components::map_components::{AlienSpawnPoint, CoolDown, CurrentTile};
 use crate::general::events::map_events::SpawnAlien;
-use crate::player::systems::spawn_players::FixSceneTransform;
+use crate::status_effects::components::StatusEffects;
 use crate::ui::spawn_ui::AddHealthBar;
 
 pub fn alien_spawner_system(
     time_res: Res<Time>,
     mut spawn_alien_mw: MessageWriter<SpawnAlien>,
     mut alien_spawn_point_query: Query<(&Position, &mut AlienSpawnPoint)>,
+    level_tracker: Res<LevelTracker>,
 ) {
+    // Scripted levels spawn through `wave_system` instead.
+    if level_tracker.has_waves() || level_tracker.aliens_left_to_spawn <= 0 {
+        return;
+    }
     for (position, mut alien_spawn_point) in alien_spawn_point_query.iter_mut() {
         if alien_spawn_point.cool_down(time_res.delta_secs()) {
             spawn_alien_mw.write(SpawnAlien {
                 position: position.0,
+                alien: default_alien(),
+                wave: None,
             });
         }
     }
@@ -38,7 +49,8 @@ pub fn spawn_aliens(
     mut spawn_alien_mr: MessageReader<SpawnAlien>,
     mut commands: Commands,
     mut add_health_bar_mw: MessageWriter<AddHealthBar>,
-    game_assets: Res<GameAssets>,
+    alien_defs: Res<AlienDefs>,
+    asset_server: Res<AssetServer>,
     mut game_tracking_mw: MessageWriter<GameTrackingEvent>,
 ) {
     if alien_counter.count >= alien_counter.max_count {
@@ -46,57 +58,85 @@ pub fn spawn_aliens(
     }
     for spawn_alien in spawn_alien_mr.read() {
         alien_counter.count += 1;
+        let def = alien_defs.get(&spawn_alien.alien);
 
         let alien_transform = Transform::from_xyz(spawn_alien.position.x, spawn_alien.position.y, spawn_alien.position.z)
-            .with_scale(Vec3::new(0.25, 0.25, 0.25))
+            .with_scale(Vec3::splat(def.scale))
             .with_rotation(Quat::from_rotation_y(PI * 2.0));
       
         let id = commands.spawn((
-            (
-                Name::from("Spider"),
-                HittableTarget {},
-                DynamicMovement {},
-                FixSceneTransform::new(
-                    Vec3::new(0.0, -0.35, 0.0),
-                    Quat::from_euler(EulerRot::YXZ, 180.0f32.to_radians(), 0.0, 0.0),
-                    Vec3::new(0.5, 0.5, 0.5),
-                ),
-                CharacterControl::new(1.0, 3.0, 1.0),
-                SceneRoot(game_assets.alien_scene.clone()),
-                alien_transform,
-                Friction::new(0.0),
-                AngularDamping(1.0),
-                LinearDamping(0.9),
-                RigidBody::Dynamic,
-                Collider::capsule(1.0, 1.0),
-                LockedAxes::new().lock_rotation_x().lock_rotation_z(),
-                CollisionLayers::new(
-                    [CollisionLayer::Alien],
-                    [
-                        CollisionLayer::Ball,
-                        CollisionLayer::Impassable,
-                        CollisionLayer::Floor,
-                        CollisionLayer::Alien,
-                        CollisionLayer::Player,
-                        CollisionLayer::AlienGoal,
-                        CollisionLayer::Sensor,
-                        CollisionLayer::PlayerAimSensor,
-                    ]),
-            ),
-            (
-                CurrentTile::default(),
-                CurrentAnimationKey::new("aliens".into(), AnimationKey::Walk),
-                Alien {},
-                AvoidWallsData::new(0.125, 0.125, 0.125, 5.0),
-                ApproachAndAttackPlayerData::default(),
-                MoveTowardsGoalData { path: None },
-                AlienSightShape::default(),
-                Attack::default(),
-                Health::default(),
-                WindWakerShaderBuilder::default().build(),
-            )
+            Name::from(def.key.clone()),
+            HittableTarget {},
+            def.model_fix.fix_scene_transform(),
+            SceneRoot(asset_server.load(def.model.clone())),
+            alien_transform,
+            Collider::capsule(def.collider.0, def.collider.1),
+            CurrentTile::default(),
+            CurrentAnimationKey::new(def.animations.clone(), AnimationKey::Walk),
+            Alien {},
+            Attack { damage_range: def.attack },
+            Health { health: def.health, max_health: def.health },
+            Bounty(def.bounty),
+            StatusEffects::default(),
+            WindWakerShaderBuilder::default().build(),
         )).id();
-      
+
+        let mut alien_commands = commands.entity(id);
+        match def.movement {
+            AlienMovement::Walk => {
+                alien_commands.insert((
+                    DynamicMovement {},
+                    CharacterControl::new(def.speed, def.turn_speed, 1.0),
+                    Friction::new(0.0),
+                    AngularDamping(1.0),
+                    LinearDamping(0.9),
+                    RigidBody::Dynamic,
+                    LockedAxes::new().lock_rotation_x().lock_rotation_z(),
+                    CollisionLayers::new(
+                        [CollisionLayer::Alien],
+                        [
+                            CollisionLayer::Ball,
+                            CollisionLayer::Impassable,
+                            CollisionLayer::Floor,
+                            CollisionLayer::Alien,
+                            CollisionLayer::Player,
+                            CollisionLayer::AlienGoal,
+                            CollisionLayer::Sensor,
+                            CollisionLayer::PlayerAimSensor,
+                        ]),
+                    AvoidWallsData::new(0.125, 0.125, 0.125, 5.0),
+                    AlienBehavior::new(def.flee_below),
+                    AlienSightShape::new(def.sight.radius, def.sight.height, def.sight.range),
+                ));
+                if def.behaviours.seek_goal {
+                    alien_commands.insert(MoveTowardsGoalData);
+                }
+                if def.behaviours.chase_player {
+                    alien_commands.insert(ApproachAndAttackPlayerData::default());
+                }
+                if def.behaviours.destroy_map {
+                    alien_commands.insert(Wrecker { damage: def.wreck_damage });
+                }
+                if let Some(ranged) = &def.ranged {
+                    alien_commands.insert(RangedAttack::new(ranged));
+                }
+            }
+            AlienMovement::Fly { altitude } => {
+                // Out of reach of everything but anti-air: only projectiles and tower
+                // sensors that carry the Flyer layer touch it.
+                alien_commands.insert((
+                    Flyer { altitude, speed: def.speed },
+                    RigidBody::Kinematic,
+                    CollisionLayers::new(
+                        [CollisionLayer::Flyer],
+                        [CollisionLayer::Ball, CollisionLayer::Sensor]),
+                ));
+            }
+        }
+        if let Some(wave) = spawn_alien.wave {
+            alien_commands.insert(FromWave(wave));
+        }
+
         add_health_bar_mw.write(AddHealthBar {
             entity: id,
             name: "ALIEN",
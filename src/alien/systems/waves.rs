@@ -0,0 +1,94 @@
+use bevy::log::info;
+use bevy::math::Vec3;
+use bevy::prelude::{MessageReader, MessageWriter, Res, ResMut, Time};
+use crate::alien::alien_defs::AlienDefs;
+use crate::building::systems::ToWorldCoordinates;
+use crate::economy::resources::Wallet;
+use crate::game_state::score_keeper::{LevelTracker, WavePhase, EARLY_CALL_BONUS_PER_SECOND};
+use crate::general::events::map_events::{CallNextWave, SpawnAlien};
+use crate::general::resources::map_resources::MapGraph;
+use crate::general::systems::map_systems::TileDefinitions;
+use crate::map::campaign::Campaign;
+
+/// Runs the level's wave script: counts down each build phase, then releases every group of
+/// the wave on its own delay and interval. Once a wave is fully out the next build phase starts.
+pub fn wave_system(
+    time: Res<Time>,
+    mut level_tracker: ResMut<LevelTracker>,
+    mut spawn_alien_mw: MessageWriter<SpawnAlien>,
+    map_graph: Res<MapGraph>,
+    tile_defs: Res<TileDefinitions>,
+) {
+    if !level_tracker.has_waves() {
+        return;
+    }
+    let delta = time.delta_secs();
+    let level_tracker = &mut *level_tracker;
+    let wave_index = level_tracker.wave;
+    let next_phase = match &mut level_tracker.wave_phase {
+        WavePhase::Building { remaining } => {
+            *remaining -= delta;
+            (*remaining <= 0.0).then_some(WavePhase::Spawning { elapsed: 0.0, spawned: Vec::new() })
+        }
+        WavePhase::Spawning { elapsed, spawned } => {
+            *elapsed += delta;
+            let Some(wave) = wave_index.checked_sub(1).and_then(|index| level_tracker.waves.get(index)) else { return };
+            spawned.resize(wave.groups.len(), 0);
+            for (group, spawned) in wave.groups.iter().zip(spawned.iter_mut()) {
+                if *elapsed < group.delay {
+                    continue;
+                }
+                let due = (((*elapsed - group.delay) / group.interval.max(0.01)) as u32 + 1).min(group.count);
+                let spawn_point = group.spawn_point % map_graph.spawn_points.len().max(1);
+                let Some(tile) = map_graph.spawn_points.get(spawn_point) else {
+                    *spawned = group.count;
+                    continue;
+                };
+                let position = tile.to_world_coords(&tile_defs) + Vec3::new(0.0, -tile_defs.wall_height, 0.0);
+                while *spawned < due {
+                    *spawned += 1;
+                    spawn_alien_mw.write(SpawnAlien {
+                        position,
+                        alien: group.alien.clone(),
+                        wave: Some(wave_index),
+                    });
+                }
+            }
+            let finished = wave.groups.iter().zip(spawned.iter()).all(|(group, spawned)| *spawned >= group.count);
+            finished.then(|| if wave_index < level_tracker.waves.len() {
+                WavePhase::Building { remaining: level_tracker.build_phase_seconds }
+            } else {
+                WavePhase::Done
+            })
+        }
+        WavePhase::Done => None,
+    };
+
+    if let Some(phase) = next_phase {
+        if matches!(phase, WavePhase::Spawning { .. }) {
+            level_tracker.wave += 1;
+            info!("Wave {}/{} incoming", level_tracker.wave, level_tracker.waves.len());
+        }
+        level_tracker.wave_phase = phase;
+    }
+}
+
+/// Ends the build phase now, paying `EARLY_CALL_BONUS_PER_SECOND` for every second skipped.
+pub fn call_next_wave_system(
+    mut call_next_wave_mr: MessageReader<CallNextWave>,
+    mut level_tracker: ResMut<LevelTracker>,
+    mut wallet: ResMut<Wallet>,
+) {
+    for _ in call_next_wave_mr.read() {
+        let WavePhase::Building { remaining } = &mut level_tracker.wave_phase else { continue };
+        let bonus = (remaining.max(0.0) * EARLY_CALL_BONUS_PER_SECOND).round() as u32;
+        *remaining = 0.0;
+        wallet.earn(bonus);
+        info!("Called the next wave early (+{bonus})");
+    }
+}
+
+/// Warns at startup about wave groups naming an alien with no definition.
+pub fn check_wave_aliens(alien_defs: Res<AlienDefs>, campaign: Res<Campaign>) {
+    alien_defs.check_campaign(&campaign);
+}
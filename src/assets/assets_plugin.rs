@@ -18,7 +18,6 @@ impl Plugin for AssetsPlugin {
 pub struct GameAssets {
     pub player_scene: Handle<Scene>,
     pub ball_scene: Handle<Scene>,
-    pub alien_scene: Handle<Scene>,
     pub alien_construct: Handle<Scene>,
     pub player_gltf: Handle<Gltf>,
     pub alien_gltf: Handle<Gltf>,
@@ -44,7 +43,6 @@ fn load_assets(
 ) {
     load_player_assets(&asset_server, &mut game_assets, &model_settings, &char_folder);
     game_assets.ball_scene = asset_server.load("ball_fab.glb#Scene0");
-    game_assets.alien_scene = asset_server.load("quaternius/alien.glb#Scene0");
     game_assets.alien_construct = asset_server.load("player.glb#Scene0");
     game_assets.alien_gltf = asset_server.load("quaternius/alien.glb");
 }
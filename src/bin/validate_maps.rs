@@ -0,0 +1,31 @@
+//! Headless check of every map in `assets/maps/`.
+//!
+//! Run with `cargo run --bin validate_maps [dir]`. Exits non-zero if any map has errors.
+use std::path::PathBuf;
+use aliens_vs_suburbia::map::map_validator;
+
+fn main() {
+    let dir = std::env::args().nth(1).map(PathBuf::from).unwrap_or_else(|| PathBuf::from("assets/maps"));
+    let results = map_validator::validate_maps_dir(&dir, &["campaign.ron"]);
+    if results.is_empty() {
+        eprintln!("No maps found in {}", dir.display());
+        std::process::exit(1);
+    }
+
+    let mut failed = 0;
+    for (path, errors) in &results {
+        if errors.is_empty() {
+            println!("ok    {}", path.display());
+        } else {
+            failed += 1;
+            println!("FAIL  {}", path.display());
+            for error in errors {
+                println!("      - {error}");
+            }
+        }
+    }
+    println!("{} maps checked, {failed} with errors", results.len());
+    if failed > 0 {
+        std::process::exit(1);
+    }
+}
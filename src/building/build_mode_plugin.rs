@@ -1,9 +1,9 @@
 use bevy::app::{App, Plugin, Update};
 use bevy::prelude::{in_state, IntoScheduleConfigs};
-use crate::building::systems::{build_tower_system, building_mode, change_build_indicator, enter_build_mode, execute_build, exit_build_mode, init_build_indicator_tint, update_build_indicator_tint};
+use crate::building::systems::{build_tower_system, building_mode, change_build_indicator, draw_route_preview, enter_build_mode, execute_build, exit_build_mode, init_build_indicator_tint, obstacle_command_system, RouteBlockCache, RoutePreview, update_build_indicator_tint, update_route_block_cache, update_route_preview};
 use crate::game_state::GameState;
-use crate::general::systems::map_systems::{add_tile_to_map, remove_tile_from_map};
-use crate::player::events::building_events::{AddTile, ChangeBuildIndicator, EnterBuildMode, ExecuteBuild, ExitBuildMode, RemoveTile};
+use crate::general::systems::map_systems::{add_tile_to_map, remove_tile_from_map, update_flow_field};
+use crate::player::events::building_events::{AddTile, ChangeBuildIndicator, CycleTargeting, EnterBuildMode, ExecuteBuild, ExitBuildMode, ObstacleCommand, RemoveTile, UpgradeTower};
 use crate::towers::events::BuildTower;
 
 pub struct BuildModeEventsPlugin;
@@ -15,9 +15,14 @@ impl Plugin for BuildModeEventsPlugin {
             .add_message::<ExitBuildMode>()
             .add_message::<ExecuteBuild>()
             .add_message::<ChangeBuildIndicator>()
+            .add_message::<CycleTargeting>()
+            .add_message::<UpgradeTower>()
+            .add_message::<ObstacleCommand>()
             .add_message::<RemoveTile>()
             .add_message::<AddTile>()
-            .add_message::<BuildTower>();
+            .add_message::<BuildTower>()
+            .init_resource::<RoutePreview>()
+            .init_resource::<RouteBlockCache>();
     }
 }
 #[allow(dead_code)]
@@ -55,13 +60,17 @@ impl Plugin for StatefulBuildModePlugin {
                     enter_build_mode,
                     exit_build_mode,
                     building_mode,
-                    execute_build,
+                    execute_build.after(update_route_block_cache),
                     remove_tile_from_map,
                     add_tile_to_map,
                     change_build_indicator,
                     build_tower_system,
+                    obstacle_command_system,
                     init_build_indicator_tint,
-                    update_build_indicator_tint,
+                    update_route_block_cache.after(update_flow_field),
+                    update_build_indicator_tint.after(update_route_block_cache),
+                    update_route_preview.after(add_tile_to_map).after(remove_tile_from_map),
+                    draw_route_preview.after(update_route_preview),
                 ).run_if(in_state(GameState::InGame)),
             );
     }
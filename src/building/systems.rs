@@ -1,19 +1,25 @@
+use std::collections::HashMap;
 use bevy::asset::AssetServer;
-use bevy::log::info;
+use bevy::log::{info, warn};
 use bevy::math::{Vec2, Vec3, Vec3Swizzles};
-use bevy::prelude::{AlphaMode, Assets, Children, Color, Commands, Component, Entity, MeshMaterial3d, MessageReader, MessageWriter, Name, Query, Res, ResMut, StandardMaterial, With, Without};
+use bevy::prelude::{AlphaMode, Assets, Children, Color, Commands, Component, Entity, Gizmos, MeshMaterial3d, MessageReader, MessageWriter, Name, Query, Res, ResMut, Resource, StandardMaterial, With, Without};
 use bevy::scene::{SceneRoot, SceneInstance, SceneSpawner};
 use avian3d::prelude::{Collider, CollisionLayers, LockedAxes, Position, RigidBody, Rotation, Sensor};
 use bevy_wind_waker_shader::WindWakerShaderBuilder;
+use crate::ai::components::senses_components::{Noise, BUILD_NOISE_RADIUS};
 use crate::control::components::{ControlCommand, CharacterControl};
+use crate::economy::resources::Wallet;
 use crate::general::components::{CollisionLayer, Health};
 use crate::general::components::map_components::{CurrentTile, MapModelDefinitions};
+use crate::game_state::score_keeper::LevelTracker;
 use crate::general::resources::map_resources::MapGraph;
 use crate::general::systems::map_systems::TileDefinitions;
+use crate::map::flow_field::FlowField;
 use crate::player::components::{BuildingIndicator, IsBuildIndicator, IsBuilding, IsObstacle};
-use crate::player::events::building_events::{ChangeBuildIndicator, EnterBuildMode, ExecuteBuild, ExitBuildMode, RemoveTile};
-use crate::towers::components::{TowerSensor, TowerShooter};
+use crate::player::events::building_events::{AddTile, ChangeBuildIndicator, EnterBuildMode, ExecuteBuild, ExitBuildMode, ObstacleAction, ObstacleCommand, RemoveTile};
+use crate::towers::components::{AuraSensor, TowerLevel, TowerSensor, TowerShooter, Trap};
 use crate::towers::events::BuildTower;
+use crate::towers::tower_defs::{TowerDef, TowerDefs};
 use crate::ui::spawn_ui::AddHealthBar;
 
 /// Tracks materials cloned from the indicator model's scene children so they can be tinted.
@@ -23,14 +29,73 @@ pub struct BuildIndicatorTint {
     initialized: bool,
 }
 
+/// Spawn-to-goal routes while building: as they are now, and as they would be with a piece
+/// on the indicator tile. Indexed like `MapGraph::spawn_points`; `None` means no route.
+#[derive(Resource, Default)]
+pub struct RoutePreview {
+    pub current: Vec<Option<Vec<(usize, usize)>>>,
+    pub preview: Vec<Option<Vec<(usize, usize)>>>,
+    /// Indicator tile and whether the selected piece is walkable, as last computed.
+    pub computed_for: Option<((usize, usize), bool)>,
+}
+
+impl RoutePreview {
+    /// Steps along the shortest current route and the shortest previewed one.
+    pub fn lengths(&self) -> Option<(usize, Option<usize>)> {
+        let shortest = |routes: &[Option<Vec<(usize, usize)>>]| routes.iter()
+            .flatten()
+            .map(|route| route.len().saturating_sub(1))
+            .min();
+        Some((shortest(&self.current)?, shortest(&self.preview)))
+    }
+}
+
+/// `MapGraph::blocks_alien_route` for the tiles under build indicators, so the tint, the HUD and
+/// the build check don't each search the grid every frame. Cleared whenever the grid changes.
+#[derive(Resource, Default)]
+pub struct RouteBlockCache {
+    pub blocks: HashMap<(usize, usize), bool>,
+}
+
+impl RouteBlockCache {
+    pub fn blocks_alien_route(&self, tile: (usize, usize), map_graph: &MapGraph) -> bool {
+        self.blocks.get(&tile).copied().unwrap_or_else(|| map_graph.blocks_alien_route(tile))
+    }
+}
+
+/// The piece a builder has selected.
+pub fn selected_piece<'a>(building_indicator: &BuildingIndicator, model_defs: &MapModelDefinitions, tower_defs: &'a TowerDefs) -> Option<&'a TowerDef> {
+    let key = model_defs.build_indicators.get(building_indicator.1 as usize)?;
+    tower_defs.get(key)
+}
+
+/// Why `piece` can't be built on `tile`, or `None` if it can. Walkable pieces go on open
+/// floor and never block the route.
+pub fn build_blocked_reason(tile: (usize, usize), piece: Option<&TowerDef>, map_graph: &MapGraph, route_block_cache: &RouteBlockCache, level_tracker: &LevelTracker, wallet: &Wallet) -> Option<&'static str> {
+    let walkable = piece.is_some_and(TowerDef::walkable);
+    if piece.is_some_and(|piece| !wallet.can_afford(piece.cost)) {
+        Some("Not enough credits")
+    } else if map_graph.occupied_tiles.contains(&tile) {
+        Some("Tile is occupied")
+    } else if walkable && !map_graph.path_finding_grid.has_vertex(tile) {
+        Some("Traps go on open floor")
+    } else if !walkable && level_tracker.maze_rule && route_block_cache.blocks_alien_route(tile, map_graph) {
+        Some("Would block the aliens' route to the goal")
+    } else {
+        None
+    }
+}
+
 pub fn enter_build_mode(
     mut enter_build_mode_evr: MessageReader<EnterBuildMode>,
     mut builder_query: Query<(&CurrentTile, &Rotation), Without<IsBuilding>>,
     asset_server: Res<AssetServer>,
     tile_definitions: Res<TileDefinitions>,
+    model_defs: Res<MapModelDefinitions>,
     mut commands: Commands,
 ) {
     for start_event in enter_build_mode_evr.read() {
+        let Some(first_key) = model_defs.build_indicators.first() else { continue };
         if let Ok((current_tile, rotation)) = builder_query.get_mut(start_event.0) {
             let desired_neighbour_pos =
                 rotation
@@ -41,7 +106,7 @@ pub fn enter_build_mode(
                 &mut commands,
                 &asset_server,
                 &desired_neighbour_pos,
-                "map/obstacle.glb#Scene0",
+                model_defs.definitions[first_key].file.clone(),
                 &tile_definitions,
             );
             commands.entity(start_event.0).insert(BuildingIndicator(building_indicator, 0));
@@ -54,7 +119,7 @@ pub fn spawn_building_indicator(
     commands: &mut Commands,
     asset_server: &Res<AssetServer>,
     position: &Vec3,
-    file: &'static str,
+    file: String,
     tile_definitions: &TileDefinitions,
 ) -> Entity {
     commands.spawn((
@@ -111,15 +176,25 @@ pub fn init_build_indicator_tint(
     }
 }
 
-/// Each frame, update tint color to green (free) or red (occupied).
+/// Each frame, update tint color to green (buildable) or red (occupied, blocked by the maze rule or unaffordable).
+#[allow(clippy::too_many_arguments)]
 pub fn update_build_indicator_tint(
-    indicators: Query<(&CurrentTile, &BuildIndicatorTint), With<IsBuildIndicator>>,
+    indicators: Query<(Entity, &CurrentTile, &BuildIndicatorTint), With<IsBuildIndicator>>,
+    builder_query: Query<&BuildingIndicator, With<IsBuilding>>,
     map_graph: Res<MapGraph>,
+    route_block_cache: Res<RouteBlockCache>,
+    level_tracker: Res<LevelTracker>,
+    model_defs: Res<MapModelDefinitions>,
+    tower_defs: Res<TowerDefs>,
+    wallet: Res<Wallet>,
     mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
-    for (tile, tint) in &indicators {
+    for (entity, tile, tint) in &indicators {
         if !tint.initialized { continue; }
-        let color = if map_graph.occupied_tiles.contains(&tile.tile) {
+        let piece = builder_query.iter()
+            .find(|building_indicator| building_indicator.0 == entity)
+            .and_then(|building_indicator| selected_piece(building_indicator, &model_defs, &tower_defs));
+        let color = if build_blocked_reason(tile.tile, piece, &map_graph, &route_block_cache, &level_tracker, &wallet).is_some() {
             Color::srgba(1.0, 0.2, 0.2, 0.55)
         } else {
             Color::srgba(0.2, 1.0, 0.2, 0.55)
@@ -147,28 +222,40 @@ pub fn exit_build_mode(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn execute_build(
     mut execute_evr: MessageReader<ExecuteBuild>,
     mut remove_tile_mw: MessageWriter<RemoveTile>,
     player_build_indicator_query: Query<&BuildingIndicator>,
     building_indicator: Query<(&Position, &CurrentTile), With<IsBuildIndicator>>,
     map_graph: Res<MapGraph>,
+    route_block_cache: Res<RouteBlockCache>,
+    level_tracker: Res<LevelTracker>,
     model_defs: Res<MapModelDefinitions>,
+    tower_defs: Res<TowerDefs>,
+    mut wallet: ResMut<Wallet>,
     mut build_tower_mw: MessageWriter<BuildTower>,
+    mut noise_mw: MessageWriter<Noise>,
 ) {
     for execute_event in execute_evr.read() {
         if let Ok(build_indicator) = player_build_indicator_query.get(execute_event.0)
             && let Ok((position, current_tile)) = building_indicator.get(build_indicator.0)
-                && !map_graph.occupied_tiles.contains(&current_tile.tile) {
+            && let Some(piece) = selected_piece(build_indicator, &model_defs, &tower_defs)
+                && build_blocked_reason(current_tile.tile, Some(piece), &map_graph, &route_block_cache, &level_tracker, &wallet).is_none()
+                && wallet.spend(piece.cost) {
 
-                    let current_index = build_indicator.1;
-                    let current_key = model_defs.build_indicators[current_index as usize];
                     build_tower_mw.write(BuildTower {
                         position: position.0,
-                        model_definition_key: current_key,
+                        model_definition_key: piece.key.clone(),
+                    });
+                    noise_mw.write(Noise {
+                        position: position.0,
+                        radius: BUILD_NOISE_RADIUS,
                     });
 
-                    remove_tile_mw.write(RemoveTile(current_tile.tile));
+                    if !piece.walkable() {
+                        remove_tile_mw.write(RemoveTile(current_tile.tile));
+                    }
                 }
     }
 }
@@ -193,6 +280,7 @@ pub fn change_build_indicator(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     model_defs: Res<MapModelDefinitions>,
+    tower_defs: Res<TowerDefs>,
     tile_defs: Res<TileDefinitions>,
 ) {
     for change_build_event in change_build_indicator_evr.read() {
@@ -208,8 +296,10 @@ pub fn change_build_indicator(
             } else {
                 building_indicator.1
             };
-            let indicator_key = model_defs.build_indicators[building_indicator.1 as usize];
-            info!("Changing indicator to {}", indicator_key);
+            let indicator_key = &model_defs.build_indicators[building_indicator.1 as usize];
+            if let Some(tower_def) = tower_defs.get(indicator_key) {
+                info!("Changing indicator to {} (cost {})", tower_def.name, tower_def.cost);
+            }
 
             let p = position.0;
             commands.entity(building_indicator.0).despawn();
@@ -221,7 +311,8 @@ pub fn change_build_indicator(
                     .definitions
                     .get(indicator_key)
                     .unwrap()
-                    .file,
+                    .file
+                    .clone(),
                 &tile_defs,
             );
         }
@@ -284,37 +375,67 @@ pub fn build_tower_system(
     mut add_health_bar_mw: MessageWriter<AddHealthBar>,
     asset_server: Res<AssetServer>,
     model_defs: Res<MapModelDefinitions>,
+    tower_defs: Res<TowerDefs>,
     tile_defs: Res<TileDefinitions>,
 ) {
     for build_tower in build_tower_mr.read() {
-        let model_def = model_defs.definitions.get(build_tower.model_definition_key).unwrap();
+        let Some(model_def) = model_defs.definitions.get(&build_tower.model_definition_key) else {
+            warn!("No model definition for {}", build_tower.model_definition_key);
+            continue;
+        };
+        let Some(tower_def) = tower_defs.get(&build_tower.model_definition_key) else { continue };
         let mut ec = commands.spawn((
-            Name::from(model_def.name),
+            Name::from(model_def.name.clone()),
             IsObstacle {},
-            SceneRoot(asset_server.load(model_def.file)),
+            SceneRoot(asset_server.load(model_def.file.clone())),
             model_def.rigid_body,
             tile_defs.create_collider(model_def.width, model_def.height, model_def.depth),
             Position::from(build_tower.position),
             model_def.create_collision_layers(),
             CurrentTile::default(),
-            Health::default(),
+            Health {
+                health: tower_def.health,
+                max_health: tower_def.health,
+            },
+            TowerLevel {
+                key: tower_def.key.clone(),
+                tier: 0,
+            },
         ));
 
-        if build_tower.model_definition_key == "tower" {
+        if let Some(weapon) = &tower_def.weapon {
+            ec.insert(weapon.targeting);
             ec.with_children(|parent| {
                 parent.spawn((
                     Name::from("Sensor"),
-                    Collider::cylinder(0.5, 2.0),
-                    CollisionLayers::new([CollisionLayer::Sensor], [CollisionLayer::Alien]),
+                    Collider::cylinder(weapon.range, 2.0),
+                    weapon.sensor_collision_layers(),
                     Position::from(build_tower.position),
                     TowerSensor {},
-                    TowerShooter::new(20.0),
+                    TowerShooter::new(weapon),
                     Sensor,
                     WindWakerShaderBuilder::default().build(),
                 ));
             });
         }
 
+        if let Some(trap) = &tower_def.trap {
+            ec.insert((Trap::new(trap), Sensor));
+        }
+
+        if let Some(aura) = &tower_def.aura {
+            ec.with_children(|parent| {
+                parent.spawn((
+                    Name::from("Aura"),
+                    Collider::cylinder(aura.range, 2.0),
+                    CollisionLayers::new([CollisionLayer::Sensor], [CollisionLayer::Alien]),
+                    Position::from(build_tower.position),
+                    AuraSensor { effect: aura.effect.clone() },
+                    Sensor,
+                ));
+            });
+        }
+
         let id = ec.id();
         add_health_bar_mw.write(AddHealthBar {
             entity: id,
@@ -322,3 +443,139 @@ pub fn build_tower_system(
         });
     }
 }
+
+/// Sells, repairs or demolishes the obstacle under the builder's indicator, paying into or
+/// out of the wallet. Removing one reopens its tile for pathfinding.
+#[allow(clippy::too_many_arguments)]
+pub fn obstacle_command_system(
+    mut obstacle_command_mr: MessageReader<ObstacleCommand>,
+    mut commands: Commands,
+    builder_query: Query<&BuildingIndicator, With<IsBuilding>>,
+    indicator_query: Query<&CurrentTile, With<IsBuildIndicator>>,
+    mut obstacle_query: Query<(Entity, &CurrentTile, &TowerLevel, &mut Health), With<IsObstacle>>,
+    tower_defs: Res<TowerDefs>,
+    mut wallet: ResMut<Wallet>,
+    mut add_tile_mw: MessageWriter<AddTile>,
+) {
+    for ObstacleCommand(builder, action) in obstacle_command_mr.read() {
+        let Ok(building_indicator) = builder_query.get(*builder) else { continue };
+        let Ok(indicator_tile) = indicator_query.get(building_indicator.0) else { continue };
+        let Some((entity, tile, level, mut health)) = obstacle_query.iter_mut()
+            .find(|(_, tile, ..)| tile.tile == indicator_tile.tile) else { continue };
+        let Some(tower_def) = tower_defs.get(&level.key) else { continue };
+
+        match action {
+            ObstacleAction::Sell | ObstacleAction::Demolish => {
+                if *action == ObstacleAction::Sell {
+                    let value = tower_def.sell_value(level.tier);
+                    wallet.earn(value);
+                    info!("Sold {} for {}", tower_def.name, value);
+                }
+                commands.entity(entity).despawn();
+                if !tower_def.walkable() {
+                    add_tile_mw.write(AddTile(tile.tile));
+                }
+            }
+            ObstacleAction::Repair => {
+                if health.health >= health.max_health {
+                    continue;
+                }
+                let cost = tower_def.repair_cost(level.tier, health.health, health.max_health);
+                if !wallet.spend(cost) {
+                    info!("Repairing {} costs {}, only {} left", tower_def.name, cost, wallet.balance);
+                    continue;
+                }
+                info!("Repaired {} for {}", tower_def.name, cost);
+                health.health = health.max_health;
+            }
+        }
+    }
+}
+
+/// Fills `RouteBlockCache` for the tiles under build indicators on maze-rule levels,
+/// starting over whenever the flow field is rebuilt. Runs after `update_flow_field` so a
+/// rebuild this frame is seen before `execute_build` trusts the cache.
+pub fn update_route_block_cache(
+    indicator_query: Query<&CurrentTile, With<IsBuildIndicator>>,
+    map_graph: Res<MapGraph>,
+    flow_field: Res<FlowField>,
+    level_tracker: Res<LevelTracker>,
+    mut route_block_cache: ResMut<RouteBlockCache>,
+) {
+    if flow_field.is_changed() && !route_block_cache.blocks.is_empty() {
+        route_block_cache.blocks.clear();
+    }
+    if !level_tracker.maze_rule {
+        return;
+    }
+    for tile in indicator_query.iter() {
+        if !route_block_cache.blocks.contains_key(&tile.tile) {
+            let blocks = map_graph.blocks_alien_route(tile.tile);
+            route_block_cache.blocks.insert(tile.tile, blocks);
+        }
+    }
+}
+
+/// Recomputes the routes when the indicator moves to another tile, the selected piece changes
+/// between walkable and not, or the grid changes.
+#[allow(clippy::too_many_arguments)]
+pub fn update_route_preview(
+    builder_query: Query<&BuildingIndicator, With<IsBuilding>>,
+    indicator_query: Query<&CurrentTile, With<IsBuildIndicator>>,
+    mut add_tile_mr: MessageReader<AddTile>,
+    mut remove_tile_mr: MessageReader<RemoveTile>,
+    map_graph: Res<MapGraph>,
+    model_defs: Res<MapModelDefinitions>,
+    tower_defs: Res<TowerDefs>,
+    mut route_preview: ResMut<RoutePreview>,
+) {
+    let grid_changed = add_tile_mr.read().count() + remove_tile_mr.read().count() > 0;
+    let Some((building_indicator, blocked)) = builder_query.iter()
+        .find_map(|building_indicator| indicator_query.get(building_indicator.0).ok()
+            .map(|tile| (building_indicator, tile.tile))) else {
+        if route_preview.computed_for.is_some() {
+            *route_preview = RoutePreview::default();
+        }
+        return;
+    };
+    let walkable = selected_piece(building_indicator, &model_defs, &tower_defs).is_some_and(TowerDef::walkable);
+    if !grid_changed && route_preview.computed_for == Some((blocked, walkable)) {
+        return;
+    }
+    route_preview.computed_for = Some((blocked, walkable));
+
+    let route = |spawn: &(usize, usize), avoid: Option<(usize, usize)>| map_graph
+        .find_path_avoiding(*spawn, map_graph.goal, avoid)
+        .map(|(path, _)| path);
+    route_preview.current = map_graph.spawn_points.iter().map(|spawn| route(spawn, None)).collect();
+    // A walkable piece leaves the routes as they are.
+    route_preview.preview = if !walkable && map_graph.path_finding_grid.has_vertex(blocked) {
+        map_graph.spawn_points.iter().map(|spawn| route(spawn, Some(blocked))).collect()
+    } else {
+        route_preview.current.clone()
+    };
+}
+
+/// Draws the current routes in blue and, where placing the piece would change them, the new routes in yellow.
+pub fn draw_route_preview(
+    route_preview: Res<RoutePreview>,
+    tile_definitions: Res<TileDefinitions>,
+    mut gizmos: Gizmos,
+) {
+    let height = tile_definitions.floor_level + tile_definitions.tile_depth;
+    let mut draw = |route: &[(usize, usize)], lift: f32, color: Color| {
+        for pair in route.windows(2) {
+            let offset = Vec3::new(0.0, height + lift, 0.0);
+            gizmos.line(pair[0].to_world_coords(&tile_definitions) + offset, pair[1].to_world_coords(&tile_definitions) + offset, color);
+        }
+    };
+    for (current, preview) in route_preview.current.iter().zip(&route_preview.preview) {
+        if let Some(current) = current {
+            draw(current, 0.05, Color::srgb(0.3, 0.6, 1.0));
+        }
+        if let Some(preview) = preview
+            && Some(preview) != current.as_ref() {
+            draw(preview, 0.1, Color::srgb(1.0, 0.85, 0.2));
+        }
+    }
+}
@@ -18,6 +18,10 @@ impl Plugin for StatefulCameraPlugin {
             OnEnter(GameState::ModelShowcase),
             (spawn_camera, apply_camera_settings).chain(),
         )
+        .add_systems(
+            OnEnter(GameState::LevelEditor),
+            (spawn_camera, apply_camera_settings).chain(),
+        )
         .add_systems(
             PostUpdate,
             camera_follow
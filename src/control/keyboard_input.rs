@@ -4,8 +4,10 @@ use bevy::math::Vec3;
 use bevy::prelude::{Entity, MessageReader, MessageWriter, KeyCode, Query, With};
 use crate::animation::animation_plugin::{AnimationEvent, AnimationEventType, AnimationKey};
 use crate::control::components::{CharacterControl, ControlCommand, ControlDirection, ControlRotation, InputKeyboard};
-use crate::player::events::building_events::{ChangeBuildIndicator, EnterBuildMode, ExecuteBuild, ExitBuildMode};
+use crate::general::events::map_events::CallNextWave;
+use crate::player::events::building_events::{ChangeBuildIndicator, CycleTargeting, EnterBuildMode, ExecuteBuild, ExitBuildMode, ObstacleAction, ObstacleCommand, UpgradeTower};
 
+#[allow(clippy::too_many_arguments)]
 pub fn keyboard_input(
     mut key_evr: MessageReader<KeyboardInput>,
     mut query: Query<(Entity, &mut CharacterControl), With<InputKeyboard>>,
@@ -13,6 +15,10 @@ pub fn keyboard_input(
     mut execute_build: MessageWriter<ExecuteBuild>,
     mut exit_build: MessageWriter<ExitBuildMode>,
     mut change_build_indicator: MessageWriter<ChangeBuildIndicator>,
+    mut cycle_targeting: MessageWriter<CycleTargeting>,
+    mut upgrade_tower: MessageWriter<UpgradeTower>,
+    mut obstacle_command: MessageWriter<ObstacleCommand>,
+    mut call_next_wave: MessageWriter<CallNextWave>,
     mut animation_ew: MessageWriter<AnimationEvent>,
 ) {
     if let Ok((entity, mut controller)) = query.single_mut() {
@@ -82,6 +88,24 @@ pub fn keyboard_input(
                     KeyCode::ArrowRight => {
                         change_build_indicator.write(ChangeBuildIndicator(entity, 1));
                     }
+                    KeyCode::KeyT if controller.triggers.contains(&ControlCommand::Build) => {
+                        cycle_targeting.write(CycleTargeting(entity));
+                    }
+                    KeyCode::KeyU if controller.triggers.contains(&ControlCommand::Build) => {
+                        upgrade_tower.write(UpgradeTower(entity));
+                    }
+                    KeyCode::KeyF if controller.triggers.contains(&ControlCommand::Build) => {
+                        obstacle_command.write(ObstacleCommand(entity, ObstacleAction::Sell));
+                    }
+                    KeyCode::KeyR if controller.triggers.contains(&ControlCommand::Build) => {
+                        obstacle_command.write(ObstacleCommand(entity, ObstacleAction::Repair));
+                    }
+                    KeyCode::Delete if controller.triggers.contains(&ControlCommand::Build) => {
+                        obstacle_command.write(ObstacleCommand(entity, ObstacleAction::Demolish));
+                    }
+                    KeyCode::Enter => {
+                        call_next_wave.write(CallNextWave);
+                    }
                     _ => {}
                 }
             }
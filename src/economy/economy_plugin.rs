@@ -0,0 +1,22 @@
+use bevy::app::{App, Plugin, Update};
+use bevy::prelude::{in_state, IntoScheduleConfigs};
+use crate::alien::systems::spawn_aliens::spawn_aliens;
+use crate::economy::resources::Wallet;
+use crate::economy::systems::{kill_reward_system, wave_clear_reward_system};
+use crate::game_state::GameState;
+
+pub struct EconomyPlugin;
+
+impl Plugin for EconomyPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Wallet>()
+            .add_systems(
+                Update,
+                (
+                    kill_reward_system,
+                    wave_clear_reward_system.after(spawn_aliens),
+                ).run_if(in_state(GameState::InGame)),
+            );
+    }
+}
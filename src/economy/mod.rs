@@ -0,0 +1,3 @@
+pub(crate) mod economy_plugin;
+pub(crate) mod resources;
+pub(crate) mod systems;
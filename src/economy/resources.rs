@@ -0,0 +1,30 @@
+use bevy::prelude::Resource;
+
+/// Bounty for alien types that don't set their own.
+pub const KILL_REWARD: u32 = 5;
+
+/// The team's shared credits. Building, upgrading and repairing spend them;
+/// kills, cleared waves, early wave calls and selling earn them.
+#[derive(Resource, Debug, Default)]
+pub struct Wallet {
+    pub balance: u32,
+}
+
+impl Wallet {
+    pub fn can_afford(&self, cost: u32) -> bool {
+        self.balance >= cost
+    }
+
+    /// Takes `cost` if there is enough, returning whether it was paid.
+    pub fn spend(&mut self, cost: u32) -> bool {
+        if !self.can_afford(cost) {
+            return false;
+        }
+        self.balance -= cost;
+        true
+    }
+
+    pub fn earn(&mut self, amount: u32) {
+        self.balance += amount;
+    }
+}
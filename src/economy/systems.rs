@@ -0,0 +1,40 @@
+use bevy::log::info;
+use bevy::prelude::{MessageReader, Query, ResMut, With};
+use crate::alien::components::general::{Alien, FromWave};
+use crate::economy::resources::Wallet;
+use crate::game_state::score_keeper::{GameTrackingEvent, LevelTracker, WavePhase};
+
+/// Pays the bounty of every alien killed, as recorded by whatever dealt the killing blow.
+pub fn kill_reward_system(
+    mut game_tracking_mr: MessageReader<GameTrackingEvent>,
+    mut wallet: ResMut<Wallet>,
+) {
+    for event in game_tracking_mr.read() {
+        if let GameTrackingEvent::AlienKilled { bounty, .. } = event {
+            wallet.earn(*bounty);
+        }
+    }
+}
+
+/// Pays the level's wave reward for every wave that has finished spawning and has no aliens
+/// left, once per wave, even when an early call started the next wave before it was cleared.
+/// Runs after `spawn_aliens` so aliens sent this frame already count.
+pub fn wave_clear_reward_system(
+    mut level_tracker: ResMut<LevelTracker>,
+    mut wallet: ResMut<Wallet>,
+    alien_query: Query<&FromWave, With<Alien>>,
+) {
+    let level_tracker = &mut *level_tracker;
+    let still_spawning = matches!(level_tracker.wave_phase, WavePhase::Spawning { .. }).then_some(level_tracker.wave);
+    for (index, cleared) in level_tracker.waves_cleared.iter_mut().enumerate().take(level_tracker.wave) {
+        let wave = index + 1;
+        if *cleared
+            || still_spawning == Some(wave)
+            || alien_query.iter().any(|from_wave| from_wave.0 == wave) {
+            continue;
+        }
+        *cleared = true;
+        wallet.earn(level_tracker.wave_reward);
+        info!("Wave {wave} cleared (+{})", level_tracker.wave_reward);
+    }
+}
@@ -8,7 +8,8 @@ impl Plugin for ClearGameEntitiesPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_systems(OnExit(GameState::InGame), clear_game_entities)
-            .add_systems(OnExit(GameState::ModelShowcase), clear_game_entities);
+            .add_systems(OnExit(GameState::ModelShowcase), clear_game_entities)
+            .add_systems(OnExit(GameState::LevelEditor), clear_game_entities);
     }
 }
 
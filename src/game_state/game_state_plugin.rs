@@ -22,11 +22,15 @@ use crate::map::map_plugins::StatefulMapPlugin;
 use crate::player::player_plugin::PlayerPlugin;
 use crate::settings::plugin::SettingsPlugin;
 use crate::model_settings::plugin::ModelSettingsPlugin;
-use crate::towers::systems::shoot_alien_system;
+use crate::towers::systems::{aura_tower_system, cycle_tower_targeting, shoot_alien_system, trap_system, upgrade_tower_system};
 use crate::ui::ui_plugin::UiPlugin;
 use crate::poly_pizza::plugin::PolyPizzaPlugin;
+use crate::projectiles::projectile_plugin::ProjectilePlugin;
+use crate::status_effects::status_effect_plugin::StatusEffectPlugin;
+use crate::economy::economy_plugin::EconomyPlugin;
 use crate::character_creator::plugin::CharacterCreatorPlugin;
 use crate::asset_browser::plugin::AssetBrowserPlugin;
+use crate::level_editor::plugin::LevelEditorPlugin;
 use crate::sprite_billboard::plugin::SpriteBillboardPlugin;
 
 pub struct GamePlugin;
@@ -58,17 +62,29 @@ impl Plugin for GamePlugin {
                 CharacterCreatorPlugin,
                 SpriteBillboardPlugin,
                 AssetBrowserPlugin,
+                LevelEditorPlugin,
+                ProjectilePlugin,
+                StatusEffectPlugin,
+                EconomyPlugin,
             ))
             .add_systems(
                 OnEnter(GameState::InGame),
                 spawn_lights,
             )
+            .add_systems(
+                OnEnter(GameState::LevelEditor),
+                spawn_lights,
+            )
             .add_systems(
                 Update,
                 (
                     throwing,
                     collision_handling_system,
                     shoot_alien_system,
+                    aura_tower_system,
+                    trap_system,
+                    cycle_tower_targeting,
+                    upgrade_tower_system,
                     spawn_death_effects.before(health_monitor_system),
                     health_monitor_system,
                     tick_death_effects,
@@ -13,4 +13,5 @@ pub enum GameState {
     CharacterCreator,
     PolyPizza,
     AssetBrowser,
+    LevelEditor,
 }
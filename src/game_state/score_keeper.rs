@@ -2,6 +2,7 @@ use bevy::app::{App, Plugin, Update};
 use bevy::prelude::{Component, Entity, Message, MessageReader, MessageWriter, ResMut, Resource,
                     in_state, IntoScheduleConfigs, Query};
 use crate::game_state::GameState;
+use crate::map::campaign::{Campaign, LevelEntry, Wave};
 use crate::ui::spawn_ui::GotoState;
 
 #[allow(dead_code)]
@@ -34,6 +35,20 @@ pub enum LevelState {
     Failed,
 }
 
+/// Bonus per second of build phase skipped by calling the next wave early.
+pub const EARLY_CALL_BONUS_PER_SECOND: f32 = 1.0;
+
+/// Where a scripted level is in its waves.
+#[derive(Debug, Clone)]
+pub enum WavePhase {
+    /// Building time left before the next wave.
+    Building { remaining: f32 },
+    /// The current wave is spawning; `spawned` counts per group.
+    Spawning { elapsed: f32, spawned: Vec<u32> },
+    /// Every wave has been sent.
+    Done,
+}
+
 #[derive(Debug, Resource)]
 pub struct LevelTracker {
     #[allow(dead_code)]
@@ -41,11 +56,21 @@ pub struct LevelTracker {
     pub aliens_to_spawn: i32,
     pub aliens_left_to_spawn: i32,
     pub aliens_killed: i32,
-    #[allow(dead_code)]
     pub spawn_rate_per_minute: f32,
     pub level_state: LevelState,
     pub aliens_reached_goal: i32,
     pub aliens_win_cut_off: i32,
+    pub maze_rule: bool,
+    /// The level's wave script; empty for continuous spawning.
+    pub waves: Vec<Wave>,
+    /// Waves started so far, so the one in progress during `Spawning`.
+    pub wave: usize,
+    pub wave_phase: WavePhase,
+    pub build_phase_seconds: f32,
+    /// Per wave, whether all its aliens have been dealt with and its reward paid.
+    pub waves_cleared: Vec<bool>,
+    /// Credits for clearing a wave.
+    pub wave_reward: u32,
 }
 
 impl Default for LevelTracker {
@@ -59,24 +84,47 @@ impl Default for LevelTracker {
             level_state: LevelState::NotStarted,
             aliens_reached_goal: 0,
             aliens_win_cut_off: 600,
+            maze_rule: false,
+            waves: Vec::new(),
+            wave: 0,
+            wave_phase: WavePhase::Done,
+            build_phase_seconds: 0.0,
+            waves_cleared: Vec::new(),
+            wave_reward: 0,
         }
     }
 }
 
 impl LevelTracker {
-    #[allow(dead_code)]
-    pub fn update(level_name: String, aliens_to_spawn: i32, spawn_rate_per_minute: f32, aliens_win_cutoff: i32) -> Self {
+    pub fn update(level: &LevelEntry) -> Self {
+        let aliens_to_spawn = level.total_aliens();
+        let wave_phase = if level.waves.is_empty() {
+            WavePhase::Done
+        } else {
+            WavePhase::Building { remaining: level.build_phase_seconds }
+        };
         Self {
-            level_name,
+            level_name: level.name.clone(),
             aliens_to_spawn,
             aliens_left_to_spawn: aliens_to_spawn,
             aliens_killed: 0,
-            spawn_rate_per_minute,
+            spawn_rate_per_minute: level.spawn_rate_per_minute,
             level_state: LevelState::NotStarted,
             aliens_reached_goal: 0,
-            aliens_win_cut_off: aliens_win_cutoff,
+            aliens_win_cut_off: level.aliens_win_cut_off,
+            maze_rule: level.maze_rule,
+            waves: level.waves.clone(),
+            wave: 0,
+            wave_phase,
+            build_phase_seconds: level.build_phase_seconds,
+            waves_cleared: vec![false; level.waves.len()],
+            wave_reward: level.wave_reward,
         }
     }
+
+    pub fn has_waves(&self) -> bool {
+        !self.waves.is_empty()
+    }
 }
 
 #[derive(Debug, Message, Clone)]
@@ -84,7 +132,8 @@ pub enum GameTrackingEvent {
     PlayerAdded(Entity),
     #[allow(dead_code)]
     PlayerRemoved(Entity),
-    AlienKilled(Entity),
+    /// `by` is whoever gets the kill; `bounty` is the alien's, read before it is despawned.
+    AlienKilled { by: Entity, bounty: u32 },
     ShotFired(Entity),
     ShotHit(Entity),
     AlienSpawned,
@@ -117,8 +166,8 @@ pub fn game_tracking_event_system(
         match event {
             GameTrackingEvent::PlayerAdded(_player) => {}
             GameTrackingEvent::PlayerRemoved(_) => {}
-            GameTrackingEvent::AlienKilled(player) => {
-                if let Ok(mut score) = score_query.get_mut(*player) {
+            GameTrackingEvent::AlienKilled { by, .. } => {
+                if let Ok(mut score) = score_query.get_mut(*by) {
                     score.kills += 1;
                 }
                 level_tracker.aliens_killed += 1;
@@ -145,27 +194,25 @@ pub fn game_tracking_event_system(
 
 pub fn level_state_system(
     mut level_tracker: ResMut<LevelTracker>,
+    mut campaign: ResMut<Campaign>,
     mut goto_state_mw: MessageWriter<GotoState>,
 ) {
-    if level_tracker.aliens_killed >= level_tracker.aliens_to_spawn {
-        level_tracker.level_state = LevelState::Completed;
-    }
-    if level_tracker.aliens_reached_goal >= level_tracker.aliens_win_cut_off {
-        level_tracker.level_state = LevelState::Failed;
-    }
-    if level_tracker.aliens_left_to_spawn == 0 {
-        level_tracker.level_state = LevelState::InProgress;
-    }
     match level_tracker.level_state {
         LevelState::NotStarted => {
             level_tracker.level_state = LevelState::InProgress;
         }
-        LevelState::InProgress => {}
-        LevelState::Completed => {
-            goto_state_mw.write(GotoState { state: GameState::Menu });
-        }
-        LevelState::Failed => {
-            goto_state_mw.write(GotoState { state: GameState::Menu });
+        LevelState::InProgress => {
+            if level_tracker.aliens_reached_goal >= level_tracker.aliens_win_cut_off {
+                level_tracker.level_state = LevelState::Failed;
+                goto_state_mw.write(GotoState { state: GameState::Menu });
+            } else if level_tracker.aliens_killed + level_tracker.aliens_reached_goal >= level_tracker.aliens_to_spawn {
+                level_tracker.level_state = LevelState::Completed;
+                // Re-entering InGame clears the level and loads the next campaign entry.
+                let next_state = if campaign.advance() { GameState::InGame } else { GameState::Menu };
+                goto_state_mw.write(GotoState { state: next_state });
+            }
         }
+        LevelState::Completed => {}
+        LevelState::Failed => {}
     }
 }
@@ -2,9 +2,9 @@ use bevy::prelude::{Component, Resource};
 use bevy::reflect::Reflect;
 use std::collections::HashMap;
 use avian3d::prelude::{CollisionLayers, LayerMask, RigidBody};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct DecorationItem {
     pub x: i32,
     pub y: i32,
@@ -17,26 +17,128 @@ pub struct DecorationItem {
 
 fn default_scale() -> f32 { 1.0 }
 
+/// Chance (0.0–1.0) that a free floor tile in each generator zone receives a prop.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct DecorationDensity {
+    /// Map edges and tiles next to houses: trees and hedges.
+    pub perimeter: f32,
+    /// Around the player spawn: suburban props.
+    pub player_area: f32,
+    /// Around alien spawns: alien dressing and debris.
+    pub alien_area: f32,
+    /// Everything else.
+    pub open: f32,
+    /// Road tiles next to a yard (street-grid maps only): parked cars, hydrants, mailboxes.
+    pub kerb: f32,
+}
+
+impl Default for DecorationDensity {
+    fn default() -> Self {
+        Self {
+            perimeter: 0.55,
+            player_area: 0.3,
+            alien_area: 0.35,
+            open: 0.08,
+            kerb: 0.3,
+        }
+    }
+}
+
+/// Ground type of a tile. Costs steer alien pathfinding; speed factors scale how fast
+/// an alien walks while standing on the tile.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum Terrain {
+    #[default]
+    Floor,
+    Road,
+    Lawn,
+    Rubble,
+    Mud,
+}
+
+impl Terrain {
+    /// Cheapest `move_cost` of any terrain; keeps the A* heuristic admissible.
+    pub const MIN_COST: u32 = 8;
+
+    /// Cost of stepping onto a tile of this terrain. Plain floor is 10.
+    pub fn move_cost(&self) -> u32 {
+        match self {
+            Terrain::Floor => 10,
+            Terrain::Road => 8,
+            Terrain::Lawn => 12,
+            Terrain::Rubble => 20,
+            Terrain::Mud => 30,
+        }
+    }
+
+    /// Multiplier on `CharacterControl::max_speed`.
+    pub fn speed_factor(&self) -> f32 {
+        match self {
+            Terrain::Floor => 1.0,
+            Terrain::Road => 1.2,
+            Terrain::Lawn => 0.9,
+            Terrain::Rubble => 0.7,
+            Terrain::Mud => 0.5,
+        }
+    }
+}
+
+fn default_patch_size() -> usize { 1 }
+
+/// A rectangle of tiles with a non-default terrain, anchored at its top-left tile.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TerrainPatch {
+    pub x: i32,
+    pub y: i32,
+    #[serde(default = "default_patch_size")]
+    pub width: usize,
+    #[serde(default = "default_patch_size")]
+    pub height: usize,
+    pub terrain: Terrain,
+}
+
+/// Which layout `generated: true` maps are built with.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GeneratorKind {
+    /// An open rectangle with a few house blocks carved out of it.
+    #[default]
+    Houses,
+    /// Two-lane streets and avenues (some ending in cul-de-sacs) around fenced yards,
+    /// each with a house and a driveway.
+    StreetGrid,
+}
+
 fn default_map_width() -> usize { 14 }
 fn default_map_height() -> usize { 24 }
 
-#[derive(Deserialize, Clone, Debug, Default)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct MapFile {
     /// When true, `tiles` and `decorations` are ignored and the map is procedurally generated from `seed`.
     #[serde(default)]
     pub generated: bool,
     #[serde(default)]
     pub seed: u64,
+    /// Layout used when `generated: true`.
+    #[serde(default)]
+    pub generator: GeneratorKind,
     /// Procedural map dimensions. Only used when `generated: true`.
     #[serde(default = "default_map_width")]
     pub map_width: usize,
     #[serde(default = "default_map_height")]
     pub map_height: usize,
-    /// Row-major grid; 0=void, 1=floor, 5=alien spawn, 9=alien goal, 17=player spawn.
+    /// Decoration density per zone. Only used when `generated: true`.
+    #[serde(default)]
+    pub decoration_density: DecorationDensity,
+    /// Row-major grid; 0=void, 1=floor, 3=pickup, 5=alien spawn, 9=alien goal, 17=player spawn,
+    /// 33=road (walkable floor that street props line up along).
     #[serde(default)]
     pub tiles: Vec<Vec<u8>>,
     #[serde(default)]
     pub decorations: Vec<DecorationItem>,
+    /// Terrain overrides. Road tiles (33) default to `Terrain::Road`, everything else to `Terrain::Floor`.
+    #[serde(default)]
+    pub terrain: Vec<TerrainPatch>,
 }
 
 #[derive(Component)]
@@ -46,8 +148,8 @@ pub struct Wall {}
 pub struct AlienGoal;
 
 pub struct ModelDefinition {
-    pub name: &'static str,
-    pub file: &'static str,
+    pub name: String,
+    pub file: String,
     pub width: f32,
     pub height: f32,
     pub depth: f32,
@@ -67,8 +169,9 @@ impl ModelDefinition {
 
 #[derive(Resource)]
 pub struct MapModelDefinitions {
-    pub definitions: HashMap<&'static str, ModelDefinition>,
-    pub build_indicators: Vec<&'static str>,
+    pub definitions: HashMap<String, ModelDefinition>,
+    /// Keys of the buildable pieces, in build-mode order. Filled from `TowerDefs`.
+    pub build_indicators: Vec<String>,
 }
 
 #[derive(Component, Debug, Reflect, Default)]
@@ -1,27 +1,8 @@
-pub (crate) mod map_components;
+pub mod map_components;
 
-use bevy::prelude::{Component, Entity, Reflect};
+use bevy::prelude::{Component, Reflect};
 use avian3d::prelude::PhysicsLayer;
 
-#[derive(Component)]
-pub struct Ball {
-    pub entity: Entity,
-    pub bounces: u32,
-    pub max_bounces: u32,
-    pub can_score: bool,
-}
-
-impl Ball {
-    pub(crate) fn new(entity: Entity) -> Self {
-        Self {
-            entity,
-            bounces: 0,
-            max_bounces: 5,
-            can_score: true,
-        }
-    }
-}
-
 #[derive(Component)]
 pub struct HittableTarget {}
 
@@ -38,6 +19,8 @@ pub enum CollisionLayer {
     BuildIndicator,
     Sensor,
     PlayerAimSensor,
+    /// Flying aliens. Only anti-air sensors and projectiles have it in their mask.
+    Flyer,
 }
 
 #[derive(Component, Clone, Debug, PartialEq)]
@@ -60,6 +43,15 @@ pub struct Health {
     pub max_health: i32,
 }
 
+impl Health {
+    /// Takes `amount` off and reports whether this was the blow that killed it.
+    pub fn take_damage(&mut self, amount: i32) -> bool {
+        let was_alive = self.health > 0;
+        self.health -= amount;
+        was_alive && self.health <= 0
+    }
+}
+
 impl Default for Health {
     fn default() -> Self {
         Self {
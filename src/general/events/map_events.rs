@@ -7,6 +7,13 @@ pub struct LoadMap {
     pub map: MapFile,
 }
 
+/// Sent after `RemoveTile`/`AddTile` have been applied and the `FlowField` rebuilt,
+/// listing every tile whose walkability changed this frame.
+#[derive(Message, Clone)]
+pub struct GridChanged {
+    pub tiles: Vec<(usize, usize)>,
+}
+
 #[derive(Message, Clone)]
 pub struct SpawnPlayer {
     pub position: Vec3,
@@ -15,4 +22,12 @@ pub struct SpawnPlayer {
 #[derive(Message, Clone)]
 pub struct SpawnAlien {
     pub position: Vec3,
+    /// Alien type, as named in the level's wave script.
+    pub alien: String,
+    /// Wave it belongs to, counted from 1; `None` for continuous spawning.
+    pub wave: Option<usize>,
 }
+
+/// Skips the rest of the build phase and starts the next wave, for a bonus.
+#[derive(Message, Clone)]
+pub struct CallNextWave;
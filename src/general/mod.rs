@@ -1,4 +1,5 @@
 pub(crate) mod systems;
-pub(crate) mod components;
+pub mod components;
 pub(crate) mod events;
-pub(crate) mod resources;
+pub mod resources;
+pub(crate) mod ron_dir;
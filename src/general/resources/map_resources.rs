@@ -1,10 +1,157 @@
-use std::collections::{HashSet};
+use std::collections::{HashMap, HashSet};
 use bevy::prelude::Resource;
+use pathfinding::directed::astar::astar;
+use pathfinding::directed::bfs::bfs_reach;
 use pathfinding::grid::Grid;
+use crate::general::components::map_components::{MapFile, Terrain};
+
+/// Extra path cost per point of health for breaking through an obstacle on a breach path.
+/// A full-health (100) piece weighs about as much as ten floor tiles of detour.
+pub const BREACH_COST_PER_HEALTH: u32 = 1;
 
 #[derive(Resource)]
 pub struct MapGraph {
     pub path_finding_grid: Grid,
     pub occupied_tiles: HashSet<(usize, usize)>,
-    pub goal: (usize, usize)
-}
\ No newline at end of file
+    pub goal: (usize, usize),
+    /// Alien spawn tiles (code 5), `(x, y)`. Filled by `load_map`.
+    pub spawn_points: Vec<(usize, usize)>,
+    /// Tiles whose terrain is not `Terrain::Floor`. Filled by `map_loader`, changed at runtime with `set_terrain`.
+    pub terrain: HashMap<(usize, usize), Terrain>,
+}
+
+impl MapGraph {
+    /// Rebuilds the walkable grid, terrain and goal from `map`. Every non-void tile is walkable.
+    pub fn load_map(&mut self, map: &MapFile) {
+        let rows = map.tiles.len();
+        let cols = map.tiles.first().map_or(0, |r| r.len());
+        self.path_finding_grid = Grid::new(cols, rows);
+        self.terrain.clear();
+        self.spawn_points.clear();
+        for (row, tiles) in map.tiles.iter().enumerate() {
+            for (column, t) in tiles.iter().enumerate() {
+                if *t == 0 {
+                    continue;
+                }
+                self.path_finding_grid.add_vertex((column, row));
+                match *t {
+                    5 => self.spawn_points.push((column, row)),
+                    9 => self.goal = (column, row),
+                    33 => self.set_terrain((column, row), Terrain::Road),
+                    _ => {}
+                }
+            }
+        }
+        self.path_finding_grid.enable_diagonal_mode();
+
+        for patch in &map.terrain {
+            for y in patch.y.max(0) as usize..(patch.y + patch.height as i32).max(0) as usize {
+                for x in patch.x.max(0) as usize..(patch.x + patch.width as i32).max(0) as usize {
+                    if y < rows && x < cols {
+                        self.set_terrain((x, y), patch.terrain);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn terrain_at(&self, tile: (usize, usize)) -> Terrain {
+        self.terrain.get(&tile).copied().unwrap_or_default()
+    }
+
+    pub fn set_terrain(&mut self, tile: (usize, usize), terrain: Terrain) {
+        if terrain == Terrain::Floor {
+            self.terrain.remove(&tile);
+        } else {
+            self.terrain.insert(tile, terrain);
+        }
+    }
+
+    /// Cheapest path from `from` to `to` over the walkable grid, weighted by terrain.
+    /// The returned path starts with `from`.
+    pub fn find_path(&self, from: (usize, usize), to: (usize, usize)) -> Option<(Vec<(usize, usize)>, u32)> {
+        self.find_path_avoiding(from, to, None)
+    }
+
+    /// `find_path` as if `blocked` were not walkable, for previewing a build before it happens.
+    pub fn find_path_avoiding(
+        &self,
+        from: (usize, usize),
+        to: (usize, usize),
+        blocked: Option<(usize, usize)>,
+    ) -> Option<(Vec<(usize, usize)>, u32)> {
+        if blocked == Some(from) {
+            return None;
+        }
+        astar(
+            &from,
+            |t| self.path_finding_grid.neighbours(*t)
+                .into_iter()
+                .filter(|n| Some(*n) != blocked)
+                .map(|n| (n, self.terrain_at(n).move_cost())),
+            |t| self.path_finding_grid.distance(*t, to) as u32 * Terrain::MIN_COST,
+            |t| *t == to,
+        )
+    }
+
+    /// Cheapest route from `from` to `to` when the tiles in `obstacles` can be broken through.
+    /// Each obstacle costs its terrain plus `BREACH_COST_PER_HEALTH` for every point of the health
+    /// given for it. One A* over the walkable grid and the obstacles together.
+    pub fn find_breach_path(
+        &self,
+        from: (usize, usize),
+        to: (usize, usize),
+        obstacles: &HashMap<(usize, usize), i32>,
+    ) -> Option<(Vec<(usize, usize)>, u32)> {
+        let grid = &self.path_finding_grid;
+        let passable = |t: (usize, usize)| grid.has_vertex(t) || obstacles.contains_key(&t);
+        astar(
+            &from,
+            |&(x, y)| {
+                let mut next = Vec::with_capacity(8);
+                for (dx, dy) in [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)] {
+                    let (nx, ny) = (x as i32 + dx, y as i32 + dy);
+                    if nx < 0 || ny < 0 || nx as usize >= grid.width || ny as usize >= grid.height {
+                        continue;
+                    }
+                    let n = (nx as usize, ny as usize);
+                    if !passable(n) {
+                        continue;
+                    }
+                    let breach = obstacles.get(&n).map_or(0, |health| (*health).max(0) as u32 * BREACH_COST_PER_HEALTH);
+                    next.push((n, self.terrain_at(n).move_cost() + breach));
+                }
+                next
+            },
+            |t| grid.distance(*t, to) as u32 * Terrain::MIN_COST,
+            |t| *t == to,
+        )
+    }
+
+    /// Whether removing `tile` would cut a spawn point that can currently reach the goal off
+    /// from it. Moves like the flow field and A* do, diagonals included.
+    pub fn blocks_alien_route(&self, tile: (usize, usize)) -> bool {
+        if !self.path_finding_grid.has_vertex(tile) || !self.path_finding_grid.has_vertex(self.goal) {
+            return false;
+        }
+        // Moves are symmetric, so one search out from the goal finds every spawn that reaches it.
+        let reachable_from_goal = |blocked: Option<(usize, usize)>| -> HashSet<(usize, usize)> {
+            bfs_reach(self.goal, |t| self.path_finding_grid.neighbours(*t)
+                .into_iter()
+                .filter(|n| Some(*n) != blocked))
+                .collect()
+        };
+        let before = reachable_from_goal(None);
+        let connected: Vec<&(usize, usize)> = self.spawn_points.iter()
+            .filter(|spawn| before.contains(*spawn))
+            .collect();
+        if connected.is_empty() {
+            return false;
+        }
+        if self.goal == tile {
+            return true;
+        }
+        let after = reachable_from_goal(Some(tile));
+        connected.into_iter().any(|spawn| !after.contains(spawn))
+    }
+}
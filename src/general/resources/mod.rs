@@ -1 +1 @@
-pub(crate) mod map_resources;
\ No newline at end of file
+pub mod map_resources;
\ No newline at end of file
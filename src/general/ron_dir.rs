@@ -0,0 +1,30 @@
+use std::path::Path;
+use serde::de::DeserializeOwned;
+
+/// Parses every `*.ron` in `dir`, keyed by file stem. Unreadable and unparseable files are
+/// logged and skipped; a missing directory yields nothing.
+pub fn load_ron_dir<T: DeserializeOwned>(dir: &Path) -> Vec<(String, T)> {
+    let Ok(entries) = std::fs::read_dir(dir) else { return Vec::new() };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "ron"))
+        .filter_map(|path| {
+            let key = path.file_stem()?.to_string_lossy().into_owned();
+            let text = match std::fs::read_to_string(&path) {
+                Ok(text) => text,
+                Err(e) => {
+                    bevy::log::error!("{}: {e}", path.display());
+                    return None;
+                }
+            };
+            match ron::from_str::<T>(&text) {
+                Ok(value) => Some((key, value)),
+                Err(e) => {
+                    bevy::log::error!("{}: {e}", path.display());
+                    None
+                }
+            }
+        })
+        .collect()
+}
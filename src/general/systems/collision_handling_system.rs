@@ -1,54 +1,135 @@
-use bevy::prelude::{Commands, Has, MessageReader, MessageWriter, Query, ResMut};
-use avian3d::prelude::CollisionStart;
-use crate::alien::components::general::{Alien, AlienCounter};
+use bevy::prelude::{Commands, Entity, Has, MessageReader, MessageWriter, Or, Query, ResMut, With, Without};
+use avian3d::prelude::{CollisionStart, Position};
+use crate::ai::components::senses_components::{Noise, IMPACT_NOISE_RADIUS};
+use crate::alien::components::general::{Alien, AlienCounter, Bounty, Flyer};
 use crate::game_state::score_keeper::{GameTrackingEvent};
-use crate::general::components::{Ball, Health, HittableTarget};
+use crate::general::components::{Health, HittableTarget};
+use crate::general::components::map_components::{CurrentTile, Terrain};
+use crate::general::resources::map_resources::MapGraph;
+use crate::player::components::{IsObstacle, Player};
+use crate::player::events::building_events::AddTile;
+use crate::projectiles::components::Projectile;
+use crate::status_effects::events::ApplyStatus;
 
+#[allow(clippy::too_many_arguments)]
 pub fn collision_handling_system(
     mut alien_counter: ResMut<AlienCounter>,
     mut collision_event_reader: MessageReader<CollisionStart>,
-    mut ball_query: Query<&mut Ball>,
-    mut hittable_target_query: Query<(&mut Health, &HittableTarget, Has<Alien>)>,
+    mut projectile_query: Query<(&mut Projectile, &Position)>,
+    mut hittable_target_query: Query<(&mut Health, &HittableTarget, Has<Alien>, Option<&Bounty>)>,
+    alien_query: Query<(Entity, &Position), With<Alien>>,
+    flyer_query: Query<(), With<Flyer>>,
+    player_query: Query<(), With<Player>>,
+    // What alien shots can hurt.
+    mut alien_target_query: Query<(&mut Health, &CurrentTile, Has<IsObstacle>), (Or<(With<Player>, With<IsObstacle>)>, Without<HittableTarget>)>,
+    mut map_graph: ResMut<MapGraph>,
     mut commands: Commands,
     mut game_mw: MessageWriter<GameTrackingEvent>,
+    mut apply_status_mw: MessageWriter<ApplyStatus>,
+    mut noise_mw: MessageWriter<Noise>,
+    mut add_tile_mw: MessageWriter<AddTile>,
 ) {
     for collision in collision_event_reader.read() {
-        let entity1 = collision.collider1;
-        let entity2 = collision.collider2;
-        if ball_query.contains(entity1) || ball_query.contains(entity2) {
-            let mut ball_is_first = true;
-            if let Ok(mut ball) = ball_query.get_mut(entity1) {
-                ball.bounces += 1;
-                if ball.bounces >= ball.max_bounces {
-                    commands.entity(entity1).despawn();
-                }
-            }
+        let (projectile_entity, other) = if projectile_query.contains(collision.collider1) {
+            (collision.collider1, collision.collider2)
+        } else if projectile_query.contains(collision.collider2) {
+            (collision.collider2, collision.collider1)
+        } else {
+            continue;
+        };
+        let Ok((mut projectile, projectile_position)) = projectile_query.get_mut(projectile_entity) else { continue };
+        if projectile.bounces >= projectile.max_bounces {
+            // Already spent this frame; the despawn is pending.
+            continue;
+        }
 
-            if let Ok(mut ball) = ball_query.get_mut(entity2) {
-                ball_is_first = false;
-                ball.bounces += 1;
-                if ball.bounces >= ball.max_bounces {
-                    commands.entity(entity2).despawn();
+        let hit_alien = alien_query.contains(other);
+        // Only the player's own throws give them away; tower and alien fire is background noise.
+        if projectile.bounces == 0 && !projectile.hostile && player_query.contains(projectile.shooter) {
+            noise_mw.write(Noise {
+                position: projectile_position.0,
+                radius: IMPACT_NOISE_RADIUS,
+            });
+        }
+        if projectile.can_damage() && projectile.hostile {
+            if let Ok((mut health, tile, is_obstacle)) = alien_target_query.get_mut(other)
+                && health.take_damage(projectile.damage)
+                && is_obstacle {
+                // Same as a wrecker breaking it: the tile reopens as rubble.
+                map_graph.set_terrain(tile.tile, Terrain::Rubble);
+                add_tile_mw.write(AddTile(tile.tile));
+            }
+        } else if projectile.can_damage() {
+            if let Ok((mut target_health, _, is_alien, bounty)) = hittable_target_query.get_mut(other) {
+                if projectile.can_score {
+                    projectile.can_score = false;
+                    game_mw.write(GameTrackingEvent::ShotHit(projectile.shooter));
+                }
+                if target_health.take_damage(projectile.damage) && is_alien {
+                    game_mw.write(GameTrackingEvent::AlienKilled {
+                        by: projectile.shooter,
+                        bounty: bounty.map_or(0, |bounty| bounty.0),
+                    });
+                    alien_counter.count -= 1;
                 }
+                apply_effects(&projectile, projectile_position, other, &alien_query, &mut apply_status_mw);
             }
 
-            let hittable_entity = if ball_is_first { entity2 } else { entity1 };
-            if let Ok((mut target_health, _, is_alien)) = hittable_target_query.get_mut(hittable_entity) {
-                let ball_entity = if ball_is_first { entity1 } else { entity2 };
-                if let Ok(mut ball) = ball_query.get_mut(ball_entity) {
-                    if ball.can_score {
-                        ball.can_score = false;
-                        game_mw.write(GameTrackingEvent::ShotHit(ball.entity));
-                    }
-                    if ball.bounces <= 2 {
-                        target_health.health -= 10;
-                        if target_health.health <= 0 && is_alien {
-                            game_mw.write(GameTrackingEvent::AlienKilled(ball.entity));
-                            alien_counter.count -= 1;
-                        }
+            // Splash goes off on any impact, floor included, so lobbed shots work.
+            if projectile.splash_radius > 0.0 {
+                let splashed: Vec<Entity> = alien_query.iter()
+                    .filter(|(entity, position)| *entity != other
+                        && (projectile.anti_air || !flyer_query.contains(*entity))
+                        && position.0.distance(projectile_position.0) <= projectile.splash_radius)
+                    .map(|(entity, _)| entity)
+                    .collect();
+                for entity in splashed {
+                    let Ok((mut health, _, _, bounty)) = hittable_target_query.get_mut(entity) else { continue };
+                    if health.take_damage(projectile.damage) {
+                        game_mw.write(GameTrackingEvent::AlienKilled {
+                            by: projectile.shooter,
+                            bounty: bounty.map_or(0, |bounty| bounty.0),
+                        });
+                        alien_counter.count -= 1;
                     }
+                    apply_effects(&projectile, projectile_position, entity, &alien_query, &mut apply_status_mw);
                 }
             }
         }
+
+        if projectile.piercing {
+            // Sensors pass through aliens until the pierce budget runs out, and stop at anything else.
+            if hit_alien && projectile.pierce_left > 0 {
+                projectile.pierce_left -= 1;
+                continue;
+            }
+            projectile.bounces = projectile.max_bounces;
+        } else {
+            projectile.bounces += 1;
+        }
+        if projectile.bounces >= projectile.max_bounces {
+            commands.entity(projectile_entity).despawn();
+        }
+    }
+}
+
+/// Hands the projectile's status effects to whatever it just damaged.
+fn apply_effects(
+    projectile: &Projectile,
+    projectile_position: &Position,
+    target: Entity,
+    alien_query: &Query<(Entity, &Position), With<Alien>>,
+    apply_status_mw: &mut MessageWriter<ApplyStatus>,
+) {
+    let direction = alien_query.get(target)
+        .map(|(_, position)| position.0 - projectile_position.0)
+        .unwrap_or_default();
+    for effect in &projectile.effects {
+        apply_status_mw.write(ApplyStatus {
+            target,
+            source: projectile.shooter,
+            effect: effect.clone(),
+            direction,
+        });
     }
 }
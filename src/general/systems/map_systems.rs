@@ -2,10 +2,14 @@ use bevy::asset::AssetServer;
 use bevy::math::{Quat, Vec3};
 use bevy::asset::RenderAssetUsages;
 use bevy::pbr::StandardMaterial;
-use bevy::prelude::{Assets, Color, Commands, Component, Has, Mesh, Mesh3d, MeshMaterial3d, MessageReader, MessageWriter, Name, Query, Res, ResMut, Resource, Transform};
+use bevy::prelude::{error, info, Assets, Color, Commands, Component, Has, Mesh, Mesh3d, MeshMaterial3d, MessageReader, MessageWriter, Name, Query, Res, ResMut, Resource, Transform};
 use bevy::mesh::{Indices, PrimitiveTopology};
 use bevy::scene::SceneRoot;
 
+/// Marks every entity spawned by `map_loader`, so a map can be torn down without leaving the state.
+#[derive(Component)]
+pub struct MapEntity;
+
 /// Marks a visual wall entity for occlusion testing by the camera system.
 #[derive(Component)]
 pub struct WallOccluder;
@@ -18,13 +22,19 @@ pub struct WallMaterials {
 }
 use avian3d::prelude::{Collider, CollisionLayers, Position, RigidBody, Rotation};
 use flagset::{flags, FlagSet};
-use pathfinding::grid::Grid;
 use std::collections::{BTreeMap, HashSet};
 use std::f32::consts::PI;
-use crate::alien::components::general::AlienCounter;
+use crate::alien::components::general::{AlienCounter, Flyer};
+use crate::economy::resources::Wallet;
+use crate::game_state::score_keeper::LevelTracker;
+use crate::map::campaign::Campaign;
+use crate::map::flow_field::FlowField;
+use crate::map::map_validator::validate_map;
+use crate::game_state::GameState;
+use crate::ui::spawn_ui::GotoState;
 use crate::general::components::CollisionLayer;
 use crate::general::components::map_components::{AlienGoal, AlienSpawnPoint, CurrentTile, Floor, MapModelDefinitions, Wall};
-use crate::general::events::map_events::{LoadMap, SpawnPlayer};
+use crate::general::events::map_events::{GridChanged, LoadMap, SpawnPlayer};
 use crate::general::resources::map_resources::MapGraph;
 use crate::settings::resources::GameSettings;
 use bevy::math::EulerRot;
@@ -90,13 +100,44 @@ pub struct MapDef {
     pub tiles: Vec<MapTile>,
 } //No data needed now
 
-pub fn load_map_one(mut send_event: MessageWriter<LoadMap>) {
-    let text = std::fs::read_to_string("assets/maps/level_01.ron")
-        .expect("assets/maps/level_01.ron not found");
-    let mut map: crate::general::components::map_components::MapFile =
-        ron::from_str(&text).expect("Failed to parse assets/maps/level_01.ron");
+/// Loads the campaign's current level: resets the level tracker and wallet from the manifest
+/// entry and sends its `MapFile` to `map_loader`.
+pub fn load_campaign_level(
+    mut send_event: MessageWriter<LoadMap>,
+    campaign: Res<Campaign>,
+    mut level_tracker: ResMut<LevelTracker>,
+    mut alien_counter: ResMut<AlienCounter>,
+    mut wallet: ResMut<Wallet>,
+    mut goto_state_mw: MessageWriter<GotoState>,
+) {
+    let level = campaign.current_level();
+    info!("Loading {} ({})", level.name, level.map);
+    *level_tracker = LevelTracker::update(level);
+    wallet.balance = level.starting_credits;
+    alien_counter.count = 0;
+
+    let path = format!("assets/{}", level.map);
+    let map = std::fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|text| ron::from_str::<crate::general::components::map_components::MapFile>(&text).map_err(|e| e.to_string()));
+    let mut map = match map {
+        Ok(map) => map,
+        Err(e) => {
+            error!("{path}: {e}");
+            goto_state_mw.write(GotoState { state: GameState::Menu });
+            return;
+        }
+    };
     if map.generated {
-        map = crate::map::map_generator::generate_suburb_map(map.seed, map.map_width, map.map_height);
+        map = crate::map::map_generator::generate_map(&map);
+    }
+    let errors = validate_map(&map);
+    if !errors.is_empty() {
+        for e in errors {
+            error!("{path}: {e}");
+        }
+        goto_state_mw.write(GotoState { state: GameState::Menu });
+        return;
     }
     send_event.write(LoadMap { map });
 }
@@ -193,13 +234,14 @@ pub fn map_loader(
     tile_defs: Res<TileDefinitions>,
     model_defs: Res<MapModelDefinitions>,
     game_settings: Res<GameSettings>,
+    level_tracker: Res<LevelTracker>,
 ) {
     for load_map in load_map_event_reader.read() {
         let map_file = &load_map.map;
         let m = &map_file.tiles;
         let rows = m.len();
         let cols = m[0].len();
-        map_graph.path_finding_grid = Grid::new(cols, rows);
+        map_graph.load_map(map_file);
         let checks = [
             [-1, 0],
             [1, 0],
@@ -211,7 +253,6 @@ pub fn map_loader(
             for (column, t) in rows.iter().enumerate() {
                 let mut flag_val: FlagSet<TileFlags> = TileFlags::Floor.into();
                 if *t != 0 {
-                    map_graph.path_finding_grid.add_vertex((column, row));
                     if column == 0 {
                         flag_val |= TileFlags::WallWest | TileFlags::AnyWall;
                     }
@@ -262,8 +303,6 @@ pub fn map_loader(
             }
         }
 
-        map_graph.path_finding_grid.enable_diagonal_mode();
-
         let map = MapDef {
             tiles,
         };
@@ -320,6 +359,7 @@ pub fn map_loader(
                         tile_defs.tile_width * (row + max_row) as f32 / 2.0,
                     );
                     commands.spawn((
+                        MapEntity,
                         Name::from(format!("Floor Collider {}:{} {}x{}", col, row, w as i32, h as i32)),
                         Floor {},
                         floor_model_def.rigid_body,
@@ -350,6 +390,7 @@ pub fn map_loader(
             mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
             mesh.insert_indices(Indices::U32(indices));
             commands.spawn((
+                MapEntity,
                 Name::from("Floor"),
                 Floor {},
                 Mesh3d(meshes.add(mesh)),
@@ -401,6 +442,7 @@ pub fn map_loader(
                         let pos = Vec3::new(center_x, -tile_defs.wall_height, z);
                         let rot = tile_defs.get_wall_rotation(dir);
                         commands.spawn((
+                            MapEntity,
                             Wall {},
                             wall_model_def.rigid_body,
                             tile_defs.create_wall_collider_merged(count),
@@ -410,10 +452,11 @@ pub fn map_loader(
                             WindWakerShaderBuilder::default().build(),
                         ));
                         commands.spawn((
+                            MapEntity,
                             Name::from(format!("Wall {:?} {}:{} count{}", dir, row, c1, count as i32)),
                             WallOccluder,
                             WallMaterials::default(),
-                            SceneRoot(asset_server.load(wall_model_def.file)),
+                            SceneRoot(asset_server.load(wall_model_def.file.clone())),
                             Transform::from_translation(pos).with_rotation(rot).with_scale(Vec3::new(count, 1.0, 1.0)),
                         ));
                     }
@@ -441,6 +484,7 @@ pub fn map_loader(
                         let pos = Vec3::new(x, -tile_defs.wall_height, center_z);
                         let rot = tile_defs.get_wall_rotation(dir);
                         commands.spawn((
+                            MapEntity,
                             Wall {},
                             wall_model_def.rigid_body,
                             tile_defs.create_wall_collider_merged(count),
@@ -450,10 +494,11 @@ pub fn map_loader(
                             WindWakerShaderBuilder::default().build(),
                         ));
                         commands.spawn((
+                            MapEntity,
                             Name::from(format!("Wall {:?} {}:{} count{}", dir, col, r1, count as i32)),
                             WallOccluder,
                             WallMaterials::default(),
-                            SceneRoot(asset_server.load(wall_model_def.file)),
+                            SceneRoot(asset_server.load(wall_model_def.file.clone())),
                             Transform::from_translation(pos).with_rotation(rot).with_scale(Vec3::new(count, 1.0, 1.0)),
                         ));
                     }
@@ -465,8 +510,9 @@ pub fn map_loader(
             if tile.features.contains(TileFlags::AlienSpawnPoint) {
                 alien_counter.max_count = 100;
                 commands.spawn((
+                    MapEntity,
                     Name::from(format!("Alien Spawn Point{}:{}", tile.x, tile.y)),
-                    AlienSpawnPoint::new(2.0),
+                    AlienSpawnPoint::new(level_tracker.spawn_rate_per_minute),
                     SceneRoot(game_assets.alien_construct.clone()),
                     RigidBody::Static,
                     WindWakerShaderBuilder::default().build(),
@@ -476,8 +522,8 @@ pub fn map_loader(
                 ));
             }
             if tile.features.contains(TileFlags::AlienGoal) {
-                map_graph.goal = (tile.x as usize, tile.y as usize);
                 commands.spawn((
+                    MapEntity,
                     Name::from(format!("Alien Goal {}:{}", tile.x, tile.y)),
                     AlienGoal,
                     SceneRoot(game_assets.alien_construct.clone()),
@@ -505,6 +551,7 @@ pub fn map_loader(
             // dec.scale is expressed in player units; multiply by player_unit to get world-unit scale
             let world_scale = dec.scale * game_settings.player_unit;
             commands.spawn((
+                MapEntity,
                 Name::from(format!("Decoration {}:{} {}", dec.x, dec.y, dec.model)),
                 SceneRoot(asset_server.load(format!("{}#Scene0", dec.model))),
                 Transform::from_translation(pos)
@@ -515,15 +562,24 @@ pub fn map_loader(
     }
 }
 
+/// The tile under a world position; the inverse of `ToWorldCoordinates`.
+pub fn world_to_tile(position: Vec3, tile_definitions: &TileDefinitions) -> (usize, usize) {
+    (
+        ((position.x + tile_definitions.tile_width / 2.0) / tile_definitions.tile_size) as usize,
+        ((position.z + tile_definitions.tile_width / 2.0) / tile_definitions.tile_size) as usize,
+    )
+}
+
 pub fn update_current_tile_system(
-    mut current_tile_query: Query<(&Position, &mut CurrentTile, Has<IsBuildIndicator>)>,
+    mut current_tile_query: Query<(&Position, &mut CurrentTile, Has<IsBuildIndicator>, Has<Flyer>)>,
     tile_definitions: Res<TileDefinitions>,
     mut map_graph: ResMut<MapGraph>,
 ) {
     map_graph.occupied_tiles.clear();
-    for (position, mut current_tile, is_build_indicator) in current_tile_query.iter_mut() {
-        current_tile.tile = ((((position.0.x + tile_definitions.tile_width / 2.0) / tile_definitions.tile_size) as usize), (((position.0.z + tile_definitions.tile_width / 2.0) / tile_definitions.tile_size) as usize));
-        if !is_build_indicator {
+    for (position, mut current_tile, is_build_indicator, is_flyer) in current_tile_query.iter_mut() {
+        current_tile.tile = world_to_tile(position.0, &tile_definitions);
+        // Flyers pass over the tile, so building there is still fine.
+        if !is_build_indicator && !is_flyer {
             map_graph.occupied_tiles.insert(current_tile.tile);
         }
     }
@@ -546,3 +602,26 @@ pub fn add_tile_to_map(
         map_graph.path_finding_grid.add_vertex(add_tile_event.0);
     }
 }
+
+/// Rebuilds the shared `FlowField` once per frame in which the walkable grid changed
+/// (a map was loaded or tiles were added or removed), then announces the changed tiles.
+pub fn update_flow_field(
+    mut load_map_mr: MessageReader<LoadMap>,
+    mut remove_tile_mr: MessageReader<RemoveTile>,
+    mut add_tile_mr: MessageReader<AddTile>,
+    map_graph: Res<MapGraph>,
+    mut flow_field: ResMut<FlowField>,
+    mut grid_changed_mw: MessageWriter<GridChanged>,
+) {
+    // Drain every reader so old messages don't trigger a rebuild next frame.
+    let loaded = load_map_mr.read().count() > 0;
+    let tiles: Vec<(usize, usize)> = remove_tile_mr.read().map(|RemoveTile(t)| *t)
+        .chain(add_tile_mr.read().map(|AddTile(t)| *t))
+        .collect();
+    if loaded || !tiles.is_empty() {
+        *flow_field = FlowField::build(&map_graph);
+    }
+    if !tiles.is_empty() {
+        grid_changed_mw.write(GridChanged { tiles });
+    }
+}
@@ -1,48 +1,47 @@
 use bevy::math::Vec3;
-use bevy::prelude::{Commands, Entity, MessageWriter, Query, Res, Transform};
-use bevy::scene::SceneRoot;
+use bevy::prelude::{Entity, MessageWriter, Query, Res};
 use bevy::time::Time;
-use avian3d::prelude::{Collider, CollisionLayers, LinearVelocity, Position, RigidBody};
-use bevy_wind_waker_shader::WindWakerShaderBuilder;
-use crate::assets::assets_plugin::GameAssets;
+use avian3d::prelude::Position;
+use crate::ai::components::senses_components::{Noise, THROW_NOISE_RADIUS};
 use crate::control::components::{ControlCommand, CharacterControl};
 use crate::game_state::score_keeper::{GameTrackingEvent};
-use crate::general::components::{Ball, CollisionLayer};
 use crate::general::components::map_components::CoolDown;
-use crate::player::components::{AutoAim, Player};
+use crate::player::components::{AutoAim, Launcher, Player};
+use crate::projectiles::events::FireProjectile;
+
+/// Where a throw from `position` heading `direction` leaves the player's hand.
+pub fn launch_point(position: Vec3, direction: Vec3) -> Vec3 {
+    position + direction * 0.5 + Vec3::new(0.0, 0.25, 0.0)
+}
 
 pub fn throwing(
     time_res: Res<Time>,
-    mut query: Query<(Entity, &Player, &Position, &AutoAim, &mut CharacterControl)>,
-    mut commands: Commands,
-    game_assets: Res<GameAssets>,
+    mut query: Query<(Entity, &Player, &Position, &AutoAim, &Launcher, &mut CharacterControl)>,
+    mut fire_projectile_mw: MessageWriter<FireProjectile>,
     mut game_mw: MessageWriter<GameTrackingEvent>,
+    mut noise_mw: MessageWriter<Noise>,
 ) {
-    for (entity, _player, position, auto_aim, mut controller) in query.iter_mut() {
+    for (entity, _player, position, auto_aim, launcher, mut controller) in query.iter_mut() {
         if controller.triggers.contains(&ControlCommand::Throw) {
             if controller.cool_down(time_res.delta_secs()) {
-                let launch_p = position.0 + auto_aim.0 * 0.5 + Vec3::new(0.0, 0.25, 0.0);
+                let launch_p = launch_point(position.0, auto_aim.direction);
                 game_mw.write(GameTrackingEvent::ShotFired(entity));
+                noise_mw.write(Noise {
+                    position: position.0,
+                    radius: THROW_NOISE_RADIUS,
+                });
                 controller.has_thrown = true;
-                commands.spawn((
-                    Ball::new(entity),
-                    SceneRoot(game_assets.ball_scene.clone()),
-                    Transform::from_xyz(launch_p.x, launch_p.y, launch_p.z),
-                    RigidBody::Dynamic,
-                    Collider::sphere(1.0 / 16.0),
-                    WindWakerShaderBuilder::default().build(),
-                    LinearVelocity(auto_aim.0 * 12.0),
-                    CollisionLayers::new(
-                        [CollisionLayer::Ball],
-                        [
-                            CollisionLayer::Impassable,
-                            CollisionLayer::Floor,
-                            CollisionLayer::Alien,
-                            CollisionLayer::Player,
-                            CollisionLayer::AlienSpawnPoint,
-                            CollisionLayer::AlienGoal
-                        ]),
-                ));
+                fire_projectile_mw.write(FireProjectile {
+                    projectile: launcher.projectile.clone(),
+                    shooter: entity,
+                    origin: launch_p,
+                    direction: auto_aim.direction,
+                    target: auto_aim.target,
+                    aim_point: auto_aim.aim_point,
+                    damage: None,
+                    hostile: false,
+                    anti_air: false,
+                });
             }
         } else {
             controller.fire_cool_down = 0.0;
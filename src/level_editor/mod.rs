@@ -0,0 +1,4 @@
+pub mod plugin;
+pub mod state;
+pub mod systems;
+pub mod ui;
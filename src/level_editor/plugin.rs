@@ -0,0 +1,37 @@
+use bevy::app::{App, Plugin, Update};
+use bevy::prelude::{IntoScheduleConfigs, OnEnter, in_state};
+use crate::game_state::GameState;
+use crate::level_editor::state::LevelEditorState;
+use crate::level_editor::systems::{
+    draw_hovered_tile, editor_camera_system, editor_key_input, editor_mouse_input,
+    enter_level_editor, load_editor_map, rebuild_editor_map, update_hovered_tile,
+};
+use crate::level_editor::ui::{spawn_level_editor_ui, update_editor_labels};
+use crate::ui::ui_plugin::spawn_ui_camera;
+
+pub struct LevelEditorPlugin;
+
+impl Plugin for LevelEditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LevelEditorState>()
+            .add_systems(
+                OnEnter(GameState::LevelEditor),
+                (enter_level_editor, spawn_ui_camera, spawn_level_editor_ui),
+            )
+            .add_systems(
+                Update,
+                (
+                    editor_key_input,
+                    load_editor_map,
+                    editor_camera_system,
+                    update_hovered_tile,
+                    editor_mouse_input,
+                    rebuild_editor_map,
+                    draw_hovered_tile,
+                    update_editor_labels,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::LevelEditor)),
+            );
+    }
+}
@@ -0,0 +1,230 @@
+use bevy::math::Vec3;
+use bevy::prelude::Resource;
+use ron::ser::PrettyConfig;
+use crate::general::components::map_components::{DecorationItem, MapFile};
+use crate::map::map_generator::{generate_map, Prop, PROP_PALETTES};
+
+pub const MAPS_DIR: &str = "assets/maps";
+
+/// Tile codes the editor can paint; see `MapFile::tiles`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TileBrush {
+    Floor,
+    Void,
+    AlienSpawn,
+    AlienGoal,
+    PlayerSpawn,
+    Pickup,
+    Road,
+}
+
+impl TileBrush {
+    pub fn code(&self) -> u8 {
+        match self {
+            TileBrush::Floor => 1,
+            TileBrush::Void => 0,
+            TileBrush::AlienSpawn => 5,
+            TileBrush::AlienGoal => 9,
+            TileBrush::PlayerSpawn => 17,
+            TileBrush::Pickup => 3,
+            TileBrush::Road => 33,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TileBrush::Floor => "Floor",
+            TileBrush::Void => "Void",
+            TileBrush::AlienSpawn => "Alien Spawn",
+            TileBrush::AlienGoal => "Alien Goal",
+            TileBrush::PlayerSpawn => "Player Spawn",
+            TileBrush::Pickup => "Pickup",
+            TileBrush::Road => "Road",
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct LevelEditorState {
+    /// Map files relative to `assets/`, e.g. `"maps/level_01.ron"`.
+    pub files: Vec<String>,
+    pub selected: usize,
+    pub map: MapFile,
+    /// Where Ctrl+S writes to, relative to `assets/`.
+    pub save_path: String,
+    pub brush: TileBrush,
+    pub palette_index: usize,
+    pub prop_index: usize,
+    pub rotation_y: f32,
+    /// Tile under the cursor as `(col, row)`.
+    pub hovered: Option<(usize, usize)>,
+    /// Camera look-at point in world space.
+    pub focus: Vec3,
+    pub load_requested: bool,
+    /// The map changed and needs to go through `map_loader` again.
+    pub map_dirty: bool,
+    pub status: String,
+}
+
+impl Default for LevelEditorState {
+    fn default() -> Self {
+        Self {
+            files: Vec::new(),
+            selected: 0,
+            map: MapFile::default(),
+            save_path: String::new(),
+            brush: TileBrush::Floor,
+            palette_index: 0,
+            prop_index: 0,
+            rotation_y: 0.0,
+            hovered: None,
+            focus: Vec3::ZERO,
+            load_requested: false,
+            map_dirty: false,
+            status: String::new(),
+        }
+    }
+}
+
+impl LevelEditorState {
+    /// Rescans `MAPS_DIR` and selects `preferred` if it is one of the maps.
+    pub fn reset_for_enter(&mut self, preferred: &str) {
+        self.files = scan_maps();
+        self.selected = self.files.iter().position(|f| f == preferred).unwrap_or(0);
+        self.hovered = None;
+        self.load_requested = true;
+        self.status.clear();
+    }
+
+    pub fn selected_file(&self) -> Option<&str> {
+        self.files.get(self.selected).map(|s| s.as_str())
+    }
+
+    pub fn next_file(&mut self) {
+        if !self.files.is_empty() {
+            self.selected = (self.selected + 1) % self.files.len();
+            self.load_requested = true;
+        }
+    }
+
+    pub fn prev_file(&mut self) {
+        if !self.files.is_empty() {
+            self.selected = (self.selected + self.files.len() - 1) % self.files.len();
+            self.load_requested = true;
+        }
+    }
+
+    /// Reads the selected file into `map`. Generated maps are expanded by the generator and
+    /// saved next to the source as `<name>_edited.ron`, so the seed file is never overwritten.
+    pub fn load_selected(&mut self) -> Result<(), String> {
+        let file = self.selected_file().ok_or("no maps in assets/maps")?.to_string();
+        let text = std::fs::read_to_string(format!("assets/{file}")).map_err(|e| format!("{file}: {e}"))?;
+        let map: MapFile = ron::from_str(&text).map_err(|e| format!("{file}: {e}"))?;
+        if map.generated {
+            self.map = generate_map(&map);
+            self.save_path = file.replace(".ron", "_edited.ron");
+        } else {
+            self.map = map;
+            self.save_path = file;
+        }
+        self.map_dirty = true;
+        Ok(())
+    }
+
+    /// Writes `map` to `save_path`, one tile row per line, and selects the written file.
+    pub fn save(&mut self) -> Result<(), String> {
+        let config = PrettyConfig::default().depth_limit(2);
+        let text = ron::ser::to_string_pretty(&self.map, config).map_err(|e| e.to_string())?;
+        std::fs::write(format!("assets/{}", self.save_path), text)
+            .map_err(|e| format!("{}: {e}", self.save_path))?;
+        self.files = scan_maps();
+        self.selected = self.files.iter().position(|f| *f == self.save_path).unwrap_or(0);
+        Ok(())
+    }
+
+    pub fn palette(&self) -> (&'static str, &'static [Prop]) {
+        PROP_PALETTES[self.palette_index]
+    }
+
+    pub fn prop(&self) -> Prop {
+        self.palette().1[self.prop_index]
+    }
+
+    pub fn cycle_palette(&mut self, step: i32) {
+        let n = PROP_PALETTES.len() as i32;
+        self.palette_index = (self.palette_index as i32 + step).rem_euclid(n) as usize;
+        self.prop_index = 0;
+    }
+
+    pub fn cycle_prop(&mut self, step: i32) {
+        let n = self.palette().1.len() as i32;
+        self.prop_index = (self.prop_index as i32 + step).rem_euclid(n) as usize;
+    }
+
+    /// Paints `brush` at `(col, row)`. Goal and player spawn are unique, so painting one
+    /// turns the previous one back into floor. Returns true if the grid changed.
+    pub fn paint(&mut self, (col, row): (usize, usize)) -> bool {
+        let code = self.brush.code();
+        let Some(current) = self.map.tiles.get(row).and_then(|r| r.get(col)).copied() else {
+            return false;
+        };
+        if current == code {
+            return false;
+        }
+        if matches!(self.brush, TileBrush::AlienGoal | TileBrush::PlayerSpawn) {
+            for tile in self.map.tiles.iter_mut().flatten() {
+                if *tile == code {
+                    *tile = 1;
+                }
+            }
+        }
+        self.map.tiles[row][col] = code;
+        if code == 0 {
+            self.remove_decorations((col, row));
+        }
+        true
+    }
+
+    /// Places the selected prop on `(col, row)`, replacing whatever decoration was there.
+    pub fn place_prop(&mut self, (col, row): (usize, usize)) -> bool {
+        let on_floor = self.map.tiles.get(row).and_then(|r| r.get(col)).is_some_and(|t| *t != 0);
+        if !on_floor {
+            return false;
+        }
+        self.remove_decorations((col, row));
+        let (model, scale) = self.prop();
+        self.map.decorations.push(DecorationItem {
+            x: col as i32,
+            y: row as i32,
+            model: model.to_string(),
+            rotation_y: self.rotation_y,
+            scale,
+        });
+        true
+    }
+
+    pub fn remove_decorations(&mut self, (col, row): (usize, usize)) -> bool {
+        let before = self.map.decorations.len();
+        self.map.decorations.retain(|d| (d.x, d.y) != (col as i32, row as i32));
+        self.map.decorations.len() != before
+    }
+}
+
+/// All `.ron` maps in `MAPS_DIR`, relative to `assets/`, sorted. Skips the campaign manifest.
+fn scan_maps() -> Vec<String> {
+    let mut files: Vec<String> = std::fs::read_dir(MAPS_DIR)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|e| {
+            let p = e.path();
+            if p.extension()?.to_str()? != "ron" {
+                return None;
+            }
+            let name = p.file_name()?.to_str()?;
+            (name != "campaign.ron").then(|| format!("maps/{name}"))
+        })
+        .collect();
+    files.sort();
+    files
+}
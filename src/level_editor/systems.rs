@@ -0,0 +1,198 @@
+use bevy::input::ButtonState;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use crate::camera::components::{CameraOffset, GameCamera};
+use crate::game_state::GameState;
+use crate::general::events::map_events::LoadMap;
+use crate::general::systems::map_systems::{MapEntity, TileDefinitions};
+use crate::level_editor::state::{LevelEditorState, TileBrush};
+use crate::map::campaign::Campaign;
+use crate::map::map_validator::validate_map;
+
+const PAN_SPEED: f32 = 8.0;
+
+pub fn enter_level_editor(mut state: ResMut<LevelEditorState>, campaign: Res<Campaign>) {
+    state.reset_for_enter(&campaign.current_level().map);
+}
+
+pub fn load_editor_map(mut state: ResMut<LevelEditorState>, tile_defs: Res<TileDefinitions>) {
+    if !state.load_requested { return; }
+    state.load_requested = false;
+
+    if let Err(e) = state.load_selected() {
+        state.status = e;
+        return;
+    }
+    let rows = state.map.tiles.len() as f32;
+    let cols = state.map.tiles.first().map_or(0, |r| r.len()) as f32;
+    state.focus = Vec3::new(
+        tile_defs.tile_width * cols / 2.0,
+        tile_defs.floor_level,
+        tile_defs.tile_width * rows / 2.0,
+    );
+}
+
+/// Tears down the current map and hands the edited `MapFile` back to `map_loader`.
+pub fn rebuild_editor_map(
+    mut commands: Commands,
+    mut state: ResMut<LevelEditorState>,
+    map_entities: Query<Entity, With<MapEntity>>,
+    mut load_map_mw: MessageWriter<LoadMap>,
+) {
+    if !state.map_dirty { return; }
+    state.map_dirty = false;
+
+    for entity in map_entities.iter() {
+        commands.entity(entity).despawn();
+    }
+    let errors = validate_map(&state.map);
+    state.status = match errors.first() {
+        None => "Map is valid".to_string(),
+        Some(first) if errors.len() == 1 => first.to_string(),
+        Some(first) => format!("{first} (+{} more)", errors.len() - 1),
+    };
+    if !state.map.tiles.is_empty() && !state.map.tiles[0].is_empty() {
+        load_map_mw.write(LoadMap { map: state.map.clone() });
+    }
+}
+
+pub fn editor_camera_system(
+    keys: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    mut state: ResMut<LevelEditorState>,
+    mut camera_query: Query<(&mut Transform, &CameraOffset), With<GameCamera>>,
+) {
+    let Ok((mut transform, offset)) = camera_query.single_mut() else { return };
+    let ctrl = keys.pressed(KeyCode::ControlLeft) || keys.pressed(KeyCode::ControlRight);
+
+    // Pan relative to the camera's yaw, flattened onto the floor.
+    let forward = Vec3::new(-offset.0.x, 0.0, -offset.0.z).normalize_or_zero();
+    let right = forward.cross(Vec3::Y);
+    let mut dir = Vec3::ZERO;
+    if keys.pressed(KeyCode::KeyW) || keys.pressed(KeyCode::ArrowUp) { dir += forward; }
+    if (keys.pressed(KeyCode::KeyS) && !ctrl) || keys.pressed(KeyCode::ArrowDown) { dir -= forward; }
+    if keys.pressed(KeyCode::KeyD) || keys.pressed(KeyCode::ArrowRight) { dir += right; }
+    if keys.pressed(KeyCode::KeyA) || keys.pressed(KeyCode::ArrowLeft) { dir -= right; }
+    if dir != Vec3::ZERO {
+        state.focus += dir.normalize() * PAN_SPEED * time.delta_secs();
+    }
+
+    transform.translation = state.focus + offset.0;
+    transform.look_at(state.focus, Vec3::Y);
+}
+
+/// Casts the cursor onto the floor plane and stores the tile under it.
+pub fn update_hovered_tile(
+    mut state: ResMut<LevelEditorState>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+    tile_defs: Res<TileDefinitions>,
+) {
+    let hovered = cursor_tile(&state, &windows, &cameras, &tile_defs);
+    if state.hovered != hovered {
+        state.hovered = hovered;
+    }
+}
+
+fn cursor_tile(
+    state: &LevelEditorState,
+    windows: &Query<&Window, With<PrimaryWindow>>,
+    cameras: &Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+    tile_defs: &TileDefinitions,
+) -> Option<(usize, usize)> {
+    let cursor = windows.single().ok()?.cursor_position()?;
+    let (camera, camera_transform) = cameras.single().ok()?;
+    let ray = camera.viewport_to_world(camera_transform, cursor).ok()?;
+    let floor = Vec3::new(0.0, tile_defs.floor_level, 0.0);
+    let distance = ray.intersect_plane(floor, InfinitePlane3d::new(Vec3::Y))?;
+    let point = ray.get_point(distance);
+    let col = ((point.x + tile_defs.tile_width / 2.0) / tile_defs.tile_width).floor();
+    let row = ((point.z + tile_defs.tile_width / 2.0) / tile_defs.tile_width).floor();
+    if col < 0.0 || row < 0.0 {
+        return None;
+    }
+    let (col, row) = (col as usize, row as usize);
+    state.map.tiles.get(row)?.get(col)?;
+    Some((col, row))
+}
+
+pub fn editor_mouse_input(
+    mouse: Res<ButtonInput<MouseButton>>,
+    mut state: ResMut<LevelEditorState>,
+) {
+    let Some(tile) = state.hovered else { return };
+    if mouse.pressed(MouseButton::Left) && state.paint(tile) {
+        state.map_dirty = true;
+    }
+    if mouse.just_pressed(MouseButton::Right) && state.place_prop(tile) {
+        state.map_dirty = true;
+    }
+}
+
+pub fn editor_key_input(
+    mut state: ResMut<LevelEditorState>,
+    mut keyboard_reader: MessageReader<KeyboardInput>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let ctrl = keys.pressed(KeyCode::ControlLeft) || keys.pressed(KeyCode::ControlRight);
+    for event in keyboard_reader.read() {
+        if event.state != ButtonState::Pressed { continue; }
+        match &event.logical_key {
+            Key::Character(c) if ctrl && c.eq_ignore_ascii_case("s") => {
+                state.status = match state.save() {
+                    Ok(()) => format!("Saved {}", state.save_path),
+                    Err(e) => e,
+                };
+            }
+            Key::Character(c) if c == "1" => state.brush = TileBrush::Floor,
+            Key::Character(c) if c == "2" => state.brush = TileBrush::Void,
+            Key::Character(c) if c == "3" => state.brush = TileBrush::AlienSpawn,
+            Key::Character(c) if c == "4" => state.brush = TileBrush::AlienGoal,
+            Key::Character(c) if c == "5" => state.brush = TileBrush::PlayerSpawn,
+            Key::Character(c) if c == "6" => state.brush = TileBrush::Pickup,
+            Key::Character(c) if c == "7" => state.brush = TileBrush::Road,
+            Key::Character(c) if c.eq_ignore_ascii_case("q") => state.cycle_palette(-1),
+            Key::Character(c) if c.eq_ignore_ascii_case("e") => state.cycle_palette(1),
+            Key::Character(c) if c.eq_ignore_ascii_case("z") => state.cycle_prop(-1),
+            Key::Character(c) if c.eq_ignore_ascii_case("x") => state.cycle_prop(1),
+            Key::Character(c) if c.eq_ignore_ascii_case("r") => {
+                state.rotation_y = (state.rotation_y + 45.0) % 360.0;
+            }
+            Key::Delete | Key::Backspace => {
+                if let Some(tile) = state.hovered && state.remove_decorations(tile) {
+                    state.map_dirty = true;
+                }
+            }
+            Key::PageUp => state.prev_file(),
+            Key::PageDown => state.next_file(),
+            Key::Escape => next_state.set(GameState::Menu),
+            _ => {}
+        }
+    }
+}
+
+pub fn draw_hovered_tile(
+    state: Res<LevelEditorState>,
+    tile_defs: Res<TileDefinitions>,
+    mut gizmos: Gizmos,
+) {
+    let Some((col, row)) = state.hovered else { return };
+    let half = tile_defs.tile_width / 2.0;
+    let center = Vec3::new(
+        tile_defs.tile_width * col as f32,
+        tile_defs.floor_level + tile_defs.tile_depth + 0.01,
+        tile_defs.tile_width * row as f32,
+    );
+    let corners = [
+        center + Vec3::new(-half, 0.0, -half),
+        center + Vec3::new(half, 0.0, -half),
+        center + Vec3::new(half, 0.0, half),
+        center + Vec3::new(-half, 0.0, half),
+    ];
+    let color = Color::srgb(1.0, 0.85, 0.2);
+    for i in 0..4 {
+        gizmos.line(corners[i], corners[(i + 1) % 4], color);
+    }
+}
@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+use lava_ui_builder::{LavaTheme, TextTheme, UIBuilder};
+use crate::level_editor::state::LevelEditorState;
+use crate::ui::spawn_ui::StateMarker;
+
+#[derive(Component, Clone, Copy)]
+pub enum EditorLabel {
+    File,
+    Brush,
+    Prop,
+    Status,
+}
+
+pub fn spawn_level_editor_ui(commands: Commands, theme: Res<LavaTheme>) {
+    let mut ui = UIBuilder::new(commands, Some(theme.clone()));
+
+    ui.component::<StateMarker>()
+        .display_flex()
+        .flex_column()
+        .gap_px(4.0)
+        .padding_all_px(8.0)
+        .bg_color(Color::srgba(0.04, 0.07, 0.10, 0.85));
+
+    let text_theme = theme.text.clone();
+    ui.with_child(|t| {
+        t.insert_bundle(lava_ui_builder::header("Level Editor", &text_theme));
+    });
+
+    for label in [EditorLabel::File, EditorLabel::Brush, EditorLabel::Prop, EditorLabel::Status] {
+        ui.with_child(|lbl| {
+            lbl.insert_bundle(lava_ui_builder::label("", &TextTheme {
+                label_size: 13.0,
+                label_color: Color::srgb(0.55, 0.75, 1.0),
+                ..text_theme.clone()
+            }))
+            .insert(label);
+        });
+    }
+
+    for help in [
+        "[LMB] paint  [RMB] place prop  [Del] remove prop",
+        "[1-7] tile  [Q/E] palette  [Z/X] prop  [R] rotate",
+        "[WASD] pan  [PgUp/PgDn] map  [Ctrl+S] save  [Esc] back",
+    ] {
+        ui.with_child(|lbl| {
+            lbl.insert_bundle(lava_ui_builder::label(help, &TextTheme {
+                label_size: 11.0,
+                label_color: Color::srgb(0.4, 0.55, 0.4),
+                ..text_theme.clone()
+            }));
+        });
+    }
+
+    ui.build();
+}
+
+pub fn update_editor_labels(
+    state: Res<LevelEditorState>,
+    mut labels: Query<(&EditorLabel, &mut Text)>,
+) {
+    if !state.is_changed() { return; }
+    for (label, mut text) in labels.iter_mut() {
+        **text = match label {
+            EditorLabel::File => format!(
+                "{}  ->  {}",
+                state.selected_file().unwrap_or("-"),
+                state.save_path,
+            ),
+            EditorLabel::Brush => format!("Tile: {}", state.brush.label()),
+            EditorLabel::Prop => {
+                let (palette, _) = state.palette();
+                let (model, _) = state.prop();
+                let name = model.rsplit('/').next().unwrap_or(model);
+                format!("Prop: {palette} / {name}  {}°", state.rotation_y)
+            }
+            EditorLabel::Status => match state.hovered {
+                Some((col, row)) => format!("{}  [{col}:{row}]", state.status),
+                None => state.status.clone(),
+            },
+        };
+    }
+}
@@ -1,56 +1,3 @@
-use bevy::app::{App, PluginGroup};
-use bevy::{DefaultPlugins, log};
-use bevy::log::LogPlugin;
-use avian3d::prelude::PhysicsPlugins;
-use bevy_mod_outline::OutlinePlugin;
-use bevy_wind_waker_shader::flat::FlatShaderPlugin;
-use crate::ai::components::approach_and_attack_player_components::ApproachAndAttackPlayerData;
-use crate::ai::components::avoid_wall_components::AvoidWallsData;
-use camera::components::CameraOffset;
-use crate::general::components::Health;
-use crate::general::components::map_components::CurrentTile;
-use control::components::CharacterControl;
-use crate::game_state::game_state_plugin::GamePlugin;
-
-pub(crate) mod player;
-pub(crate) mod general;
-pub(crate) mod camera;
-pub(crate) mod alien;
-pub(crate) mod ai;
-pub(crate) mod towers;
-pub(crate) mod ui;
-mod control;
-mod building;
-mod map;
-pub(crate) mod game_state;
-mod animation;
-mod constants;
-mod assets;
-pub(crate) mod settings;
-pub(crate) mod model_settings;
-pub(crate) mod poly_pizza;
-pub(crate) mod character_creator;
-pub(crate) mod sprite_billboard;
-pub(crate) mod asset_browser;
-
-
 fn main() {
-    App::new()
-        .register_type::<CameraOffset>()
-        .register_type::<CurrentTile>()
-        .register_type::<CharacterControl>()
-        .register_type::<Health>()
-        .register_type::<AvoidWallsData>()
-        .register_type::<ApproachAndAttackPlayerData>()
-        .add_plugins(
-            DefaultPlugins.set(
-                LogPlugin {
-                    filter: "wgpu_core=warn,wgpu_hal=warn".into(),
-                    level: log::Level::INFO,
-                    ..Default::default()
-                }))
-        .add_plugins(PhysicsPlugins::default())
-        .add_plugins(FlatShaderPlugin::global())
-        .add_plugins(GamePlugin)
-        .run();
+    aliens_vs_suburbia::run();
 }
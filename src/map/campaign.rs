@@ -0,0 +1,154 @@
+use bevy::log::error;
+use bevy::prelude::Resource;
+use serde::Deserialize;
+use crate::alien::alien_defs::default_alien;
+
+pub const CAMPAIGN_PATH: &str = "assets/maps/campaign.ron";
+
+/// One entry in the campaign manifest: which map to load and how the level plays out.
+#[derive(Deserialize, Clone, Debug)]
+pub struct LevelEntry {
+    pub name: String,
+    /// Path relative to `assets/`, e.g. `"maps/level_01.ron"`.
+    pub map: String,
+    #[serde(default = "default_aliens_to_spawn")]
+    pub aliens_to_spawn: i32,
+    /// Spawn rate for every alien spawn point on the map.
+    #[serde(default = "default_spawn_rate")]
+    pub spawn_rate_per_minute: f32,
+    /// The level is lost once this many aliens have reached the goal.
+    #[serde(default = "default_win_cut_off")]
+    pub aliens_win_cut_off: i32,
+    /// Maze rule: refuse builds that would cut any alien spawn point off from the goal.
+    #[serde(default)]
+    pub maze_rule: bool,
+    /// Scripted waves. When empty, every spawn point spawns at `spawn_rate_per_minute`
+    /// until `aliens_to_spawn` have come out.
+    #[serde(default)]
+    pub waves: Vec<Wave>,
+    /// Seconds to build before the first wave and between waves.
+    #[serde(default = "default_build_phase")]
+    pub build_phase_seconds: f32,
+    /// Credits to build with at the start of the level.
+    #[serde(default = "default_starting_credits")]
+    pub starting_credits: u32,
+    /// Credits for every wave cleared.
+    #[serde(default = "default_wave_reward")]
+    pub wave_reward: u32,
+}
+
+/// One wave: its groups all start counting from the moment the wave begins.
+#[derive(Deserialize, Clone, Debug)]
+pub struct Wave {
+    pub groups: Vec<SpawnGroup>,
+}
+
+/// `count` aliens of one type from one spawn point, `interval` seconds apart,
+/// the first `delay` seconds into the wave.
+#[derive(Deserialize, Clone, Debug)]
+pub struct SpawnGroup {
+    #[serde(default)]
+    pub delay: f32,
+    /// Key of an `AlienDef` in `assets/aliens/`.
+    #[serde(default = "default_alien")]
+    pub alien: String,
+    pub count: u32,
+    /// Index into the map's spawn points, counted row by row from the top left.
+    /// Wraps around on maps with fewer.
+    #[serde(default)]
+    pub spawn_point: usize,
+    #[serde(default = "default_interval")]
+    pub interval: f32,
+}
+
+fn default_interval() -> f32 { 1.0 }
+fn default_build_phase() -> f32 { 20.0 }
+fn default_starting_credits() -> u32 { 100 }
+fn default_wave_reward() -> u32 { 25 }
+fn default_aliens_to_spawn() -> i32 { 10 }
+fn default_spawn_rate() -> f32 { 2.0 }
+fn default_win_cut_off() -> i32 { 10 }
+
+impl LevelEntry {
+    /// Aliens the level sends in total: the wave script's sum when it has one.
+    pub fn total_aliens(&self) -> i32 {
+        if self.waves.is_empty() {
+            self.aliens_to_spawn
+        } else {
+            self.waves.iter()
+                .flat_map(|wave| &wave.groups)
+                .map(|group| group.count as i32)
+                .sum()
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct CampaignFile {
+    pub levels: Vec<LevelEntry>,
+}
+
+/// The loaded campaign manifest plus the index of the level being played.
+#[derive(Resource, Debug)]
+pub struct Campaign {
+    pub levels: Vec<LevelEntry>,
+    pub current: usize,
+}
+
+impl Campaign {
+    /// Reads `CAMPAIGN_PATH`. Falls back to a single entry for `maps/level_01.ron`
+    /// so the game still starts without a manifest; a manifest that can't be read is logged first.
+    pub fn load() -> Self {
+        let levels = match Self::read_levels() {
+            Ok(levels) if !levels.is_empty() => levels,
+            Ok(_) => {
+                error!("{CAMPAIGN_PATH}: no levels, falling back to maps/level_01.ron");
+                vec![Self::fallback_level()]
+            }
+            Err(e) => {
+                error!("{CAMPAIGN_PATH}: {e}, falling back to maps/level_01.ron");
+                vec![Self::fallback_level()]
+            }
+        };
+        Self { levels, current: 0 }
+    }
+
+    /// The manifest's levels. Parse errors carry their line and column.
+    fn read_levels() -> Result<Vec<LevelEntry>, String> {
+        let text = std::fs::read_to_string(CAMPAIGN_PATH).map_err(|e| e.to_string())?;
+        ron::from_str::<CampaignFile>(&text)
+            .map(|file| file.levels)
+            .map_err(|e| e.to_string())
+    }
+
+    fn fallback_level() -> LevelEntry {
+        LevelEntry {
+            name: "Level 1".to_string(),
+            map: "maps/level_01.ron".to_string(),
+            aliens_to_spawn: default_aliens_to_spawn(),
+            spawn_rate_per_minute: default_spawn_rate(),
+            aliens_win_cut_off: default_win_cut_off(),
+            maze_rule: false,
+            waves: Vec::new(),
+            build_phase_seconds: default_build_phase(),
+            starting_credits: default_starting_credits(),
+            wave_reward: default_wave_reward(),
+        }
+    }
+
+    pub fn current_level(&self) -> &LevelEntry {
+        &self.levels[self.current.min(self.levels.len() - 1)]
+    }
+
+    /// Moves to the next level. Returns false (and rewinds to the first level)
+    /// when the campaign has been completed.
+    pub fn advance(&mut self) -> bool {
+        if self.current + 1 < self.levels.len() {
+            self.current += 1;
+            true
+        } else {
+            self.current = 0;
+            false
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use bevy::prelude::Resource;
+use crate::general::resources::map_resources::MapGraph;
+
+/// Shared navigation toward `MapGraph::goal`: an integration field (terrain-weighted cost to
+/// the goal from every tile) and a direction field (the neighbour to step to next). Built once
+/// per grid change instead of running A* per alien.
+#[derive(Resource, Default)]
+pub struct FlowField {
+    pub width: usize,
+    pub height: usize,
+    pub goal: (usize, usize),
+    /// Cost to reach the goal, row-major; `u32::MAX` where the goal is unreachable.
+    pub integration: Vec<u32>,
+    /// Next tile toward the goal, row-major; `None` at the goal and where it is unreachable.
+    pub directions: Vec<Option<(usize, usize)>>,
+}
+
+impl FlowField {
+    pub fn build(map_graph: &MapGraph) -> Self {
+        let grid = &map_graph.path_finding_grid;
+        let (width, height) = (grid.width, grid.height);
+        let goal = map_graph.goal;
+        let index = |(x, y): (usize, usize)| y * width + x;
+        let mut integration = vec![u32::MAX; width * height];
+        let mut directions = vec![None; width * height];
+        if !grid.has_vertex(goal) {
+            return Self { width, height, goal, integration, directions };
+        }
+
+        // Dijkstra outward from the goal. Stepping from `n` onto `t` costs `t`'s terrain,
+        // matching the edge weights `MapGraph::find_path` uses.
+        integration[index(goal)] = 0;
+        let mut open = BinaryHeap::from([Reverse((0u32, goal))]);
+        while let Some(Reverse((cost, tile))) = open.pop() {
+            if cost > integration[index(tile)] {
+                continue;
+            }
+            let step = map_graph.terrain_at(tile).move_cost();
+            for neighbour in grid.neighbours(tile) {
+                let next_cost = cost + step;
+                if next_cost < integration[index(neighbour)] {
+                    integration[index(neighbour)] = next_cost;
+                    open.push(Reverse((next_cost, neighbour)));
+                }
+            }
+        }
+
+        for y in 0..height {
+            for x in 0..width {
+                let tile = (x, y);
+                if tile == goal || integration[index(tile)] == u32::MAX {
+                    continue;
+                }
+                // Same total the Dijkstra pass minimised: step onto `n`, then its cost to the goal.
+                directions[index(tile)] = grid.neighbours(tile)
+                    .into_iter()
+                    .min_by_key(|n| (integration[index(*n)].saturating_add(map_graph.terrain_at(*n).move_cost()), *n));
+            }
+        }
+
+        Self { width, height, goal, integration, directions }
+    }
+
+    /// The tile to walk to from `tile`, or `None` at the goal or where the goal is unreachable.
+    pub fn next_tile(&self, tile: (usize, usize)) -> Option<(usize, usize)> {
+        if tile.0 >= self.width || tile.1 >= self.height {
+            return None;
+        }
+        self.directions[tile.1 * self.width + tile.0]
+    }
+
+    pub fn reaches_goal(&self, tile: (usize, usize)) -> bool {
+        self.cost_to_goal(tile) != u32::MAX
+    }
+
+    /// Terrain-weighted cost from `tile` to the goal; `u32::MAX` where it is unreachable.
+    pub fn cost_to_goal(&self, tile: (usize, usize)) -> u32 {
+        if tile.0 >= self.width || tile.1 >= self.height {
+            return u32::MAX;
+        }
+        self.integration[tile.1 * self.width + tile.0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+    use pathfinding::grid::Grid;
+    use super::*;
+    use crate::general::components::map_components::{MapFile, Terrain, TerrainPatch};
+
+    fn map_graph(map: &MapFile) -> MapGraph {
+        let mut map_graph = MapGraph {
+            path_finding_grid: Grid::new(0, 0),
+            occupied_tiles: HashSet::new(),
+            goal: (0, 0),
+            spawn_points: Vec::new(),
+            terrain: HashMap::new(),
+        };
+        map_graph.load_map(map);
+        map_graph
+    }
+
+    #[test]
+    fn direction_steps_around_mud() {
+        // Mud at (1, 0) and floor at (1, 1) are both one step from the goal.
+        let map = MapFile {
+            tiles: vec![vec![5, 1, 9], vec![1, 1, 1]],
+            terrain: vec![TerrainPatch { x: 1, y: 0, width: 1, height: 1, terrain: Terrain::Mud }],
+            ..MapFile::default()
+        };
+        let flow_field = FlowField::build(&map_graph(&map));
+        assert_eq!(flow_field.cost_to_goal((1, 0)), flow_field.cost_to_goal((1, 1)));
+        assert_eq!(flow_field.next_tile((0, 0)), Some((1, 1)));
+        assert_eq!(flow_field.cost_to_goal((0, 0)), 2 * Terrain::Floor.move_cost());
+    }
+}
@@ -1,4 +1,6 @@
-use crate::general::components::map_components::{DecorationItem, MapFile};
+use std::collections::HashSet;
+use std::ops::Range;
+use crate::general::components::map_components::{DecorationDensity, DecorationItem, GeneratorKind, MapFile, Terrain, TerrainPatch};
 
 // ── Seeded RNG (xorshift64) ──────────────────────────────────────────────────
 
@@ -21,12 +23,11 @@ impl Rng {
     fn range(&mut self, lo: usize, hi: usize) -> usize {
         lo + (self.next() as usize % (hi - lo))
     }
-    #[allow(dead_code)]
+
     fn f32(&mut self) -> f32 {
         (self.next() >> 40) as f32 / (1u64 << 24) as f32
     }
-    
-    #[allow(dead_code)]
+
     fn prob(&mut self, p: f32) -> bool {
         self.f32() < p
     }
@@ -45,7 +46,7 @@ impl Rng {
 // Assumes poly-pizza models are ~1 world unit tall at scale=1.0; adjust GameSettings.player_unit
 // or individual values here if a model looks wrong in-game.
 
-type Prop = (&'static str, f32);
+pub(crate) type Prop = (&'static str, f32);
 
 // Trees: significantly taller than the player (3–5p)
 const TREES: &[Prop] = &[
@@ -146,6 +147,29 @@ const COMBAT: &[Prop] = &[
     ("packs/post-apocalypse/Water Tower.glb", 2.5),
 ];
 
+// Kerbside props for street-grid maps: parked vehicles and street furniture
+const STREET: &[Prop] = &[
+    ("packs/city/Car.glb", 0.9),
+    ("packs/city/Car-unqqkULtRU.glb", 0.9),
+    ("packs/city/Police Car.glb", 0.9),
+    ("packs/city/Sports Car.glb", 0.8),
+    ("packs/city/SUV.glb", 1.1),
+    ("packs/city/Pickup Truck.glb", 1.0),
+    ("packs/city/Van.glb", 1.2),
+    ("packs/city/Fire hydrant.glb", 0.35),
+    ("packs/city/Mailbox.glb", 0.5),
+    ("packs/city/Trash Can.glb", 0.5),
+    ("packs/city/Cone.glb", 0.35),
+    ("packs/post-apocalypse/Street Light.glb", 2.0),
+];
+
+// Yard fencing: waist height (0.6p)
+const FENCES: &[Prop] = &[
+    ("packs/city/Fence.glb", 0.6),
+    ("packs/city/Fence Piece.glb", 0.6),
+    ("packs/city/Fence End.glb", 0.6),
+];
+
 // Tiny ground-level scatter (0.05–0.25p) — fills empty floor without dominating the view
 const CLUTTER: &[Prop] = &[
     ("packs/nature/Rock Medium.glb", 0.2),
@@ -167,9 +191,20 @@ const CLUTTER: &[Prop] = &[
     ("packs/post-apocalypse/Wheel.glb", 0.25),
 ];
 
+/// Named palettes for tools that let a designer pick props by hand (the level editor).
+pub(crate) const PROP_PALETTES: &[(&str, &[Prop])] = &[
+    ("Trees", TREES),
+    ("Bushes", BUSHES),
+    ("Suburban", SUBURBAN),
+    ("Alien", ALIEN_DRESSING),
+    ("Combat", COMBAT),
+    ("Clutter", CLUTTER),
+    ("Street", STREET),
+    ("Fences", FENCES),
+];
+
 // ── Zone classification ──────────────────────────────────────────────────────
 
-#[allow(dead_code)]
 #[derive(Clone, Copy, PartialEq)]
 enum Zone {
     PlayerArea,  // near player spawn — suburban props, parked cars
@@ -177,7 +212,18 @@ enum Zone {
     Perimeter,   // map edge and house-adjacent — trees, hedges
     Open,        // mid-map — mixed combat debris and suburban clutter
 }
-#[allow(dead_code)]
+
+impl Zone {
+    fn density(self, density: &DecorationDensity) -> f32 {
+        match self {
+            Zone::PlayerArea => density.player_area,
+            Zone::AlienArea => density.alien_area,
+            Zone::Perimeter => density.perimeter,
+            Zone::Open => density.open,
+        }
+    }
+}
+
 fn classify(
     row: usize,
     col: usize,
@@ -211,7 +257,7 @@ fn classify(
         Zone::Open
     }
 }
-#[allow(dead_code)]
+
 fn pick_prop(rng: &mut Rng, zone: Zone) -> (&'static str, f32) {
     let palette: &[Prop] = match zone {
         Zone::PlayerArea => match rng.range(0, 5) {
@@ -242,7 +288,7 @@ fn pick_prop(rng: &mut Rng, zone: Zone) -> (&'static str, f32) {
 
 // ── Connectivity check (BFS) ─────────────────────────────────────────────────
 
-fn is_connected(grid: &[Vec<u8>], from: (usize, usize), to: (usize, usize)) -> bool {
+pub(crate) fn is_connected(grid: &[Vec<u8>], from: (usize, usize), to: (usize, usize)) -> bool {
     if grid[from.0][from.1] == 0 || grid[to.0][to.1] == 0 {
         return false;
     }
@@ -270,6 +316,96 @@ fn is_connected(grid: &[Vec<u8>], from: (usize, usize), to: (usize, usize)) -> b
     false
 }
 
+/// Shortest 4-connected path from `from` to `to`, both ends included. Empty if unreachable.
+fn shortest_path(grid: &[Vec<u8>], from: (usize, usize), to: (usize, usize)) -> Vec<(usize, usize)> {
+    let h = grid.len();
+    let w = grid[0].len();
+    let mut came_from = vec![vec![None; w]; h];
+    let mut queue = std::collections::VecDeque::from([from]);
+    came_from[from.0][from.1] = Some(from);
+    while let Some((r, c)) = queue.pop_front() {
+        if (r, c) == to {
+            let mut path = vec![to];
+            let mut cur = to;
+            while cur != from {
+                cur = came_from[cur.0][cur.1].unwrap();
+                path.push(cur);
+            }
+            return path;
+        }
+        for (dr, dc) in [(-1i32, 0i32), (1, 0), (0, -1), (0, 1)] {
+            let nr = r as i32 + dr;
+            let nc = c as i32 + dc;
+            if nr >= 0 && (nr as usize) < h && nc >= 0 && (nc as usize) < w {
+                let (nr, nc) = (nr as usize, nc as usize);
+                if came_from[nr][nc].is_none() && grid[nr][nc] != 0 {
+                    came_from[nr][nc] = Some((r, c));
+                    queue.push_back((nr, nc));
+                }
+            }
+        }
+    }
+    Vec::new()
+}
+
+// ── Decoration pass ──────────────────────────────────────────────────────────
+
+/// The shortest route from every spawn to the goal, widened by one tile on each side
+/// (aliens move diagonally). Props are never placed here so dressing can't hide the path
+/// aliens walk. Spawn and goal tiles are part of their routes.
+fn route_corridor(
+    grid: &[Vec<u8>],
+    player: (usize, usize),
+    aliens: &[(usize, usize)],
+    goal: (usize, usize),
+) -> HashSet<(usize, usize)> {
+    let h = grid.len();
+    let w = grid[0].len();
+    let mut corridor = HashSet::new();
+    for &from in std::iter::once(&player).chain(aliens) {
+        for (r, c) in shortest_path(grid, from, goal) {
+            for nr in r.saturating_sub(1)..=(r + 1).min(h - 1) {
+                for nc in c.saturating_sub(1)..=(c + 1).min(w - 1) {
+                    corridor.insert((nr, nc));
+                }
+            }
+        }
+    }
+    corridor
+}
+
+/// Scatters props by zone on plain floor tiles, skipping everything in `keep_clear`.
+fn decorate(
+    grid: &[Vec<u8>],
+    rng: &mut Rng,
+    player: (usize, usize),
+    aliens: &[(usize, usize)],
+    density: &DecorationDensity,
+    keep_clear: &HashSet<(usize, usize)>,
+) -> Vec<DecorationItem> {
+    let mut decorations = Vec::new();
+    for row in 0..grid.len() {
+        for col in 0..grid[0].len() {
+            if grid[row][col] != 1 || keep_clear.contains(&(row, col)) {
+                continue;
+            }
+            let zone = classify(row, col, grid, player, aliens);
+            if !rng.prob(zone.density(density)) {
+                continue;
+            }
+            let (model, scale) = pick_prop(rng, zone);
+            decorations.push(DecorationItem {
+                x: col as i32,
+                y: row as i32,
+                model: model.to_string(),
+                rotation_y: rng.rotation(),
+                scale,
+            });
+        }
+    }
+    decorations
+}
+
 // ── House placement ──────────────────────────────────────────────────────────
 
 fn try_place_house(
@@ -294,34 +430,58 @@ fn try_place_house(
         let clear = (row..row + hh).all(|r| (col..col + hw).all(|c| grid[r][c] == 1));
         if !clear { continue; }
 
-        // Tentatively place
-        for r in row..row + hh {
-            for c in col..col + hw {
-                grid[r][c] = 0;
-            }
+        if place_footprint(grid, row..row + hh, col..col + hw, player, aliens, goal) {
+            return true;
         }
+    }
+    false
+}
 
-        // All alien spawns and player must still reach the goal
-        let connected = is_connected(grid, player, goal)
-            && aliens.iter().all(|&sp| is_connected(grid, sp, goal));
+/// Voids the footprint unless that would cut the player or an alien spawn off from the goal,
+/// in which case the grid is left untouched.
+fn place_footprint(
+    grid: &mut [Vec<u8>],
+    rows: Range<usize>,
+    cols: Range<usize>,
+    player: (usize, usize),
+    aliens: &[(usize, usize)],
+    goal: (usize, usize),
+) -> bool {
+    let previous: Vec<Vec<u8>> = rows.clone().map(|r| grid[r][cols.clone()].to_vec()).collect();
 
-        if connected {
-            return true;
+    // Tentatively place
+    for r in rows.clone() {
+        for c in cols.clone() {
+            grid[r][c] = 0;
         }
+    }
 
+    // All alien spawns and player must still reach the goal
+    let connected = is_connected(grid, player, goal)
+        && aliens.iter().all(|&sp| is_connected(grid, sp, goal));
+
+    if !connected {
         // Roll back
-        for r in row..row + hh {
-            for c in col..col + hw {
-                grid[r][c] = 1;
-            }
+        for (r, tiles) in rows.zip(previous) {
+            grid[r][cols.clone()].copy_from_slice(&tiles);
         }
     }
-    false
+    connected
 }
 
 // ── Public entry point ───────────────────────────────────────────────────────
 
-pub fn generate_suburb_map(seed: u64, width: usize, height: usize) -> MapFile {
+/// Expands a `generated: true` map spec into a concrete map.
+pub fn generate_map(spec: &MapFile) -> MapFile {
+    match spec.generator {
+        GeneratorKind::Houses =>
+            generate_suburb_map(spec.seed, spec.map_width, spec.map_height, &spec.decoration_density),
+        GeneratorKind::StreetGrid =>
+            generate_street_grid_map(spec.seed, spec.map_width, spec.map_height, &spec.decoration_density),
+    }
+}
+
+pub fn generate_suburb_map(seed: u64, width: usize, height: usize, density: &DecorationDensity) -> MapFile {
     let w = width.max(8);
     let h = height.max(12);
     let mut rng = Rng::new(seed);
@@ -356,13 +516,284 @@ pub fn generate_suburb_map(seed: u64, width: usize, height: usize) -> MapFile {
         try_place_house(&mut grid, &mut rng, player, &alien_spawns, goal);
     }
 
+    let keep_clear = route_corridor(&grid, player, &alien_spawns, goal);
+    let decorations = decorate(&grid, &mut rng, player, &alien_spawns, density, &keep_clear);
+
+    MapFile {
+        generated: false,
+        seed,
+        generator: GeneratorKind::Houses,
+        map_width: w,
+        map_height: h,
+        decoration_density: density.clone(),
+        tiles: grid,
+        decorations,
+        terrain: Vec::new(),
+    }
+}
+
+// ── Street grid entry point ──────────────────────────────────────────────────
+
+const ROAD: u8 = 33;
+
+/// Start offsets of two-tile-wide roads along an axis of `len` tiles: the first within
+/// three tiles of the edge, then one after every block of 4–6 tiles.
+fn road_lines(rng: &mut Rng, len: usize) -> Vec<usize> {
+    let mut lines = Vec::new();
+    let mut at = rng.range(0, 3);
+    while at + 2 <= len {
+        lines.push(at);
+        at += 2 + rng.range(4, 7);
+    }
+    lines
+}
+
+/// The gaps between roads (and between roads and the map edge), as half-open ranges.
+fn block_spans(lines: &[usize], len: usize) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    let mut start = 0;
+    for &line in lines {
+        if line > start {
+            spans.push(start..line);
+        }
+        start = line + 2;
+    }
+    if start < len {
+        spans.push(start..len);
+    }
+    spans
+}
+
+/// The road tile closest (Manhattan) to `target`, ties broken by row-major order.
+fn nearest_road(grid: &[Vec<u8>], target: (usize, usize), exclude: &[(usize, usize)]) -> Option<(usize, usize)> {
+    (0..grid.len())
+        .flat_map(|r| (0..grid[0].len()).map(move |c| (r, c)))
+        .filter(|&(r, c)| grid[r][c] == ROAD && !exclude.contains(&(r, c)))
+        .min_by_key(|&(r, c)| r.abs_diff(target.0) + c.abs_diff(target.1))
+}
+
+/// Tiles walked from the house's edge to the nearest road, excluding both ends.
+/// Empty if no road is reachable in a straight line.
+fn driveway(
+    grid: &[Vec<u8>],
+    rows: &Range<usize>,
+    cols: &Range<usize>,
+) -> Vec<(usize, usize)> {
+    let h = grid.len() as i32;
+    let w = grid[0].len() as i32;
+    let mid_r = ((rows.start + rows.end) / 2) as i32;
+    let mid_c = ((cols.start + cols.end) / 2) as i32;
+    let starts = [
+        ((rows.start as i32 - 1, mid_c), (-1, 0)),
+        ((rows.end as i32, mid_c), (1, 0)),
+        ((mid_r, cols.start as i32 - 1), (0, -1)),
+        ((mid_r, cols.end as i32), (0, 1)),
+    ];
+    let mut best: Vec<(usize, usize)> = Vec::new();
+    for ((mut r, mut c), (dr, dc)) in starts {
+        let mut path = Vec::new();
+        while r >= 0 && r < h && c >= 0 && c < w && grid[r as usize][c as usize] == 1 {
+            path.push((r as usize, c as usize));
+            r += dr;
+            c += dc;
+        }
+        let reached_road = r >= 0 && r < h && c >= 0 && c < w && grid[r as usize][c as usize] == ROAD;
+        if reached_road && (best.is_empty() || path.len() < best.len()) {
+            best = path;
+        }
+    }
+    best
+}
+
+/// Lays the map out as a street grid: two-lane streets (rows) and avenues (columns), some
+/// avenues cut short into cul-de-sacs, and the blocks between them split into fenced lots
+/// with a house and a driveway each. Road tiles are tagged `ROAD` so kerbside props line up
+/// along them. Spawns and the goal sit on roads, and every house is checked against
+/// pathfinding like `try_place_house`, so the goal stays reachable.
+pub fn generate_street_grid_map(seed: u64, width: usize, height: usize, density: &DecorationDensity) -> MapFile {
+    let w = width.max(8);
+    let h = height.max(12);
+    let mut rng = Rng::new(seed);
+    let mut grid: Vec<Vec<u8>> = vec![vec![1u8; w]; h];
+
+    let streets = road_lines(&mut rng, h);
+    let avenues = road_lines(&mut rng, w);
+    for &r in &streets {
+        for row in &mut grid[r..r + 2] {
+            row.fill(ROAD);
+        }
+    }
+    for &c in &avenues {
+        for row in grid.iter_mut() {
+            row[c..c + 2].fill(ROAD);
+        }
+    }
+
+    let row_spans = block_spans(&streets, h);
+    let col_spans = block_spans(&avenues, w);
+
+    // Cul-de-sacs: an avenue segment below a street may stop halfway and end in a turning bulb.
+    for &c in &avenues {
+        for span in &row_spans {
+            if span.start == 0 || span.len() < 4 || !rng.prob(0.35) {
+                continue;
+            }
+            let end = span.start + span.len() / 2;
+            for row in &mut grid[end..span.end] {
+                row[c..c + 2].fill(1);
+            }
+            let bulb = end - 1;
+            if c > 0 {
+                grid[bulb][c - 1] = ROAD;
+            }
+            if c + 2 < w {
+                grid[bulb][c + 2] = ROAD;
+            }
+        }
+    }
+
+    // Anchors sit on roads: player top-left, aliens top-right, goal deep in the map.
+    let player = nearest_road(&grid, (0, 0), &[]).unwrap_or((0, 0));
+    let spawn_count = rng.range(1, 4);
+    let mut alien_spawns: Vec<(usize, usize)> = Vec::new();
+    for target in [(0, w - 1), (h / 4, w - 1), (0, w / 2)].into_iter().take(spawn_count) {
+        let mut taken = alien_spawns.clone();
+        taken.push(player);
+        if let Some(spawn) = nearest_road(&grid, target, &taken) {
+            alien_spawns.push(spawn);
+        }
+    }
+    let mut taken = alien_spawns.clone();
+    taken.push(player);
+    let goal = nearest_road(&grid, (h - 5, w / 2), &taken).unwrap_or((h - 1, w / 2));
+
+    grid[player.0][player.1] = 17;
+    for &(r, c) in &alien_spawns {
+        grid[r][c] = 5;
+    }
+    grid[goal.0][goal.1] = 9;
+
+    // Lots: wide blocks split into two, each with a house inside a one-tile yard ring.
+    let mut driveways: HashSet<(usize, usize)> = HashSet::new();
+    let mut fences: Vec<((usize, usize), f32)> = Vec::new();
+    for rows in &row_spans {
+        for cols in &col_spans {
+            let lots = if cols.len() >= 7 {
+                let mid = cols.start + cols.len() / 2;
+                for r in rows.clone() {
+                    fences.push(((r, mid), 90.0));
+                }
+                vec![cols.start..mid, mid + 1..cols.end]
+            } else {
+                vec![cols.clone()]
+            };
+            for lot in lots {
+                if rows.len() < 3 || lot.len() < 3 {
+                    continue;
+                }
+                let hh = rng.range(1, 4).min(rows.len() - 2);
+                let hw = rng.range(1, 4).min(lot.len() - 2);
+                let row = rng.range(rows.start + 1, rows.end - hh);
+                let col = rng.range(lot.start + 1, lot.end - hw);
+                let (house_rows, house_cols) = (row..row + hh, col..col + hw);
+                let ring_clear = (row - 1..=row + hh)
+                    .all(|r| (col - 1..=col + hw).all(|c| grid[r][c] == 1));
+                if !ring_clear {
+                    continue;
+                }
+                if place_footprint(&mut grid, house_rows.clone(), house_cols.clone(), player, &alien_spawns, goal) {
+                    driveways.extend(driveway(&grid, &house_rows, &house_cols));
+                }
+            }
+            // Back fences along the map edge.
+            for c in cols.clone() {
+                if rows.start == 0 { fences.push(((0, c), 0.0)); }
+                if rows.end == h { fences.push(((h - 1, c), 0.0)); }
+            }
+            for r in rows.clone() {
+                if cols.start == 0 { fences.push(((r, 0), 90.0)); }
+                if cols.end == w { fences.push(((r, w - 1), 90.0)); }
+            }
+        }
+    }
+
+    let corridor = route_corridor(&grid, player, &alien_spawns, goal);
+    let mut keep_clear: HashSet<(usize, usize)> = corridor.union(&driveways).copied().collect();
+    let mut decorations = Vec::new();
+
+    for ((r, c), rotation_y) in fences {
+        if grid[r][c] != 1 || keep_clear.contains(&(r, c)) {
+            continue;
+        }
+        keep_clear.insert((r, c));
+        decorations.push(DecorationItem {
+            x: c as i32,
+            y: r as i32,
+            model: FENCES[0].0.to_string(),
+            rotation_y,
+            scale: FENCES[0].1,
+        });
+    }
+
+    // Kerbside props: road tiles that border a yard, lined up with the road.
+    for r in 0..h {
+        for c in 0..w {
+            if grid[r][c] != ROAD || keep_clear.contains(&(r, c)) {
+                continue;
+            }
+            let kerb = [(-1i32, 0i32), (1, 0), (0, -1), (0, 1)].iter().any(|&(dr, dc)| {
+                let nr = r as i32 + dr;
+                let nc = c as i32 + dc;
+                nr >= 0 && (nr as usize) < h && nc >= 0 && (nc as usize) < w
+                    && grid[nr as usize][nc as usize] == 1
+            });
+            if !kerb || !rng.prob(density.kerb) {
+                continue;
+            }
+            let along_street = streets.iter().any(|&s| r == s || r == s + 1);
+            let base = if along_street { 90.0 } else { 0.0 };
+            let (model, scale) = *rng.pick(STREET);
+            decorations.push(DecorationItem {
+                x: c as i32,
+                y: r as i32,
+                model: model.to_string(),
+                rotation_y: base + 180.0 * rng.range(0, 2) as f32,
+                scale,
+            });
+        }
+    }
+
+    decorations.extend(decorate(&grid, &mut rng, player, &alien_spawns, density, &keep_clear));
+
+    // Yards are lawn and driveways paved, so aliens prefer the streets.
+    let mut terrain: Vec<TerrainPatch> = Vec::new();
+    for rows in &row_spans {
+        for cols in &col_spans {
+            terrain.push(TerrainPatch {
+                x: cols.start as i32,
+                y: rows.start as i32,
+                width: cols.len(),
+                height: rows.len(),
+                terrain: Terrain::Lawn,
+            });
+        }
+    }
+    let mut paved: Vec<(usize, usize)> = driveways.into_iter().collect();
+    paved.sort();
+    for (r, c) in paved {
+        terrain.push(TerrainPatch { x: c as i32, y: r as i32, width: 1, height: 1, terrain: Terrain::Road });
+    }
+
     MapFile {
         generated: false,
         seed,
+        generator: GeneratorKind::StreetGrid,
         map_width: w,
         map_height: h,
+        decoration_density: density.clone(),
         tiles: grid,
-        decorations: vec![],
+        decorations,
+        terrain,
     }
 }
 
@@ -394,9 +825,12 @@ pub fn generate_showcase_map(seed: u64) -> MapFile {
     MapFile {
         generated: false,
         seed,
+        generator: GeneratorKind::Houses,
         map_width: w,
         map_height: h,
+        decoration_density: DecorationDensity::default(),
         tiles: grid,
         decorations,
+        terrain: Vec::new(),
     }
 }
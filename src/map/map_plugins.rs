@@ -1,84 +1,82 @@
 use bevy::app::{App, Plugin, Startup, Update};
 use std::collections::HashMap;
-use avian3d::prelude::{LayerMask, RigidBody};
 use pathfinding::grid::Grid;
 use std::collections::HashSet;
 use bevy::ecs::schedule::SystemCondition;
 use bevy::prelude::{in_state, IntoScheduleConfigs, OnEnter};
 use crate::alien::components::general::AlienCounter;
 use crate::game_state::GameState;
+use avian3d::prelude::{LayerMask, RigidBody};
 use crate::general::components::CollisionLayer;
 use crate::general::components::map_components::{ModelDefinition, MapModelDefinitions};
-use crate::general::events::map_events::{LoadMap, SpawnAlien, SpawnPlayer};
+use crate::general::events::map_events::{CallNextWave, GridChanged, LoadMap, SpawnAlien, SpawnPlayer};
 use crate::general::resources::map_resources::MapGraph;
-use crate::general::systems::map_systems::{load_map_one, load_map_showcase, map_loader, TileDefinitions, update_current_tile_system};
+use crate::map::campaign::Campaign;
+use crate::map::flow_field::FlowField;
+use crate::towers::tower_defs::TowerDefs;
+use crate::general::systems::map_systems::{add_tile_to_map, load_campaign_level, load_map_showcase, map_loader, remove_tile_from_map, TileDefinitions, update_current_tile_system, update_flow_field};
+
+/// The map's static pieces plus every buildable piece from `TowerDefs`.
+fn model_definitions(tower_defs: &TowerDefs) -> MapModelDefinitions {
+    let mut definitions = HashMap::from(
+        [
+            ("wall".to_string(), ModelDefinition {
+                name: "wall".to_string(),
+                file: "map/wall_small.glb#Scene0".to_string(),
+                width: 16.0,
+                height: 19.0,
+                depth: 1.0,
+                rigid_body: RigidBody::Static,
+                group: LayerMask::from([CollisionLayer::Impassable]),
+                mask: LayerMask::from([CollisionLayer::Ball, CollisionLayer::Alien, CollisionLayer::Player]),
+            }),
+            ("floor".to_string(), ModelDefinition {
+                name: "floor".to_string(),
+                file: "map/floor_small.glb#Scene0".to_string(),
+                width: 16.0,
+                height: 1.0,
+                depth: 16.0,
+                rigid_body: RigidBody::Static,
+                group: LayerMask::from([CollisionLayer::Floor]),
+                mask: LayerMask::from([CollisionLayer::Ball, CollisionLayer::Alien, CollisionLayer::Player]),
+            }),
+        ]);
+    for def in &tower_defs.defs {
+        definitions.insert(def.key.clone(), def.model_definition());
+    }
+    MapModelDefinitions {
+        definitions,
+        build_indicators: tower_defs.defs.iter().map(|def| def.key.clone()).collect(),
+    }
+}
 
 pub struct NonStateMapStuff;
 
 impl Plugin for NonStateMapStuff {
     fn build(&self, app: &mut App) {
+        let tower_defs = TowerDefs::load();
         app
             .add_message::<LoadMap>()
             .add_message::<SpawnPlayer>()
             .add_message::<SpawnAlien>()
+            .add_message::<CallNextWave>()
+            .add_message::<GridChanged>()
+            .insert_resource(model_definitions(&tower_defs))
+            .insert_resource(tower_defs)
             .insert_resource(
-                MapModelDefinitions {
-                    definitions: HashMap::from(
-                        [
-                            ("wall", ModelDefinition {
-                                name: "wall",
-                                file: "map/wall_small.glb#Scene0",
-                                width: 16.0,
-                                height: 19.0,
-                                depth: 1.0,
-                                rigid_body: RigidBody::Static,
-                                group: LayerMask::from([CollisionLayer::Impassable]),
-                                mask: LayerMask::from([CollisionLayer::Ball, CollisionLayer::Alien, CollisionLayer::Player]),
-                            }),
-                            ("floor", ModelDefinition {
-                                name: "floor",
-                                file: "map/floor_small.glb#Scene0",
-                                width: 16.0,
-                                height: 1.0,
-                                depth: 16.0,
-                                rigid_body: RigidBody::Static,
-                                group: LayerMask::from([CollisionLayer::Floor]),
-                                mask: LayerMask::from([CollisionLayer::Ball, CollisionLayer::Alien, CollisionLayer::Player]),
-                            }),
-                            ("obstacle", ModelDefinition {
-                                name: "obstacle",
-                                file: "map/obstacle.glb#Scene0",
-                                width: 16.0,
-                                height: 4.0,
-                                depth: 16.0,
-                                rigid_body: RigidBody::Kinematic,
-                                group: LayerMask::from([CollisionLayer::Impassable]),
-                                mask: LayerMask::from([CollisionLayer::Ball, CollisionLayer::Alien, CollisionLayer::Player]),
-                            }),
-                            ("tower", ModelDefinition {
-                                name: "tower",
-                                file: "map/tower_balls.glb#Scene0",
-                                width: 16.0,
-                                height: 8.0,
-                                depth: 16.0,
-                                rigid_body: RigidBody::Kinematic,
-                                group: LayerMask::from([CollisionLayer::Impassable]),
-                                mask: LayerMask::from([CollisionLayer::Ball, CollisionLayer::Alien, CollisionLayer::Player]),
-                            }),
-                        ]),
-                    build_indicators: vec!["obstacle", "tower"],
-
-                }
-            ).insert_resource(
             TileDefinitions::new(1.0,
                                  32.0,
                                  9.5,
                                  1.0,))
             .insert_resource(AlienCounter::new(50))
+            .insert_resource(Campaign::load())
+            .init_resource::<FlowField>()
             .insert_resource(MapGraph {
                 path_finding_grid: Grid::new(0, 0),
                 occupied_tiles: HashSet::new(),
                 goal: (0, 0),
+                spawn_points: Vec::new(),
+                terrain: HashMap::new(),
             });
     }
 }
@@ -89,14 +87,26 @@ impl Plugin for StatefulMapPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_plugins(NonStateMapStuff)
-            .add_systems(OnEnter(GameState::InGame), load_map_one)
+            .add_systems(OnEnter(GameState::InGame), load_campaign_level)
             .add_systems(OnEnter(GameState::ModelShowcase), load_map_showcase)
             .add_systems(
                 Update, (
                     update_current_tile_system,
                     map_loader,
                 )
-                    .run_if(in_state(GameState::InGame).or(in_state(GameState::ModelShowcase))),
+                    .run_if(
+                        in_state(GameState::InGame)
+                            .or(in_state(GameState::ModelShowcase))
+                            .or(in_state(GameState::LevelEditor)),
+                    ),
+            )
+            .add_systems(
+                Update,
+                update_flow_field
+                    .after(map_loader)
+                    .after(add_tile_to_map)
+                    .after(remove_tile_from_map)
+                    .run_if(in_state(GameState::InGame)),
             );
     }
 }
@@ -110,7 +120,7 @@ impl Plugin for MapPlugin {
             .add_systems(
                 Startup,
                 (
-                    load_map_one,
+                    load_campaign_level,
                 ),
             )
             .add_systems(Update, (
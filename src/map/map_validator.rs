@@ -0,0 +1,263 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use crate::general::components::map_components::MapFile;
+use crate::map::map_generator::{generate_map, is_connected};
+
+/// Tile codes `map_loader` understands; see `MapFile::tiles`.
+pub const VALID_TILE_CODES: &[u8] = &[0, 1, 3, 5, 9, 17, 33];
+
+/// A problem found in a `MapFile`. Grid positions are `(row, col)`, decorations use their own `x`/`y`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MapError {
+    Unreadable(String),
+    Parse(String),
+    EmptyGrid,
+    RaggedRow { row: usize, expected: usize, found: usize },
+    UnknownTileCode { row: usize, col: usize, code: u8 },
+    MissingPlayerSpawn,
+    MissingGoal,
+    UnreachableGoal { from: (usize, usize) },
+    DecorationOffGrid { index: usize, x: i32, y: i32 },
+    DecorationOnVoid { index: usize, x: i32, y: i32 },
+    TerrainOffGrid { index: usize, x: i32, y: i32 },
+}
+
+impl fmt::Display for MapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MapError::Unreadable(e) => write!(f, "could not read file: {e}"),
+            MapError::Parse(e) => write!(f, "could not parse RON: {e}"),
+            MapError::EmptyGrid => write!(f, "tile grid is empty"),
+            MapError::RaggedRow { row, expected, found } =>
+                write!(f, "row {row} has {found} tiles, expected {expected}"),
+            MapError::UnknownTileCode { row, col, code } =>
+                write!(f, "unknown tile code {code} at row {row}, col {col}"),
+            MapError::MissingPlayerSpawn => write!(f, "no player spawn (17)"),
+            MapError::MissingGoal => write!(f, "no alien goal (9)"),
+            MapError::UnreachableGoal { from: (row, col) } =>
+                write!(f, "goal cannot be reached from spawn at row {row}, col {col}"),
+            MapError::DecorationOffGrid { index, x, y } =>
+                write!(f, "decoration #{index} at {x}:{y} is outside the grid"),
+            MapError::DecorationOnVoid { index, x, y } =>
+                write!(f, "decoration #{index} at {x}:{y} sits on a void tile"),
+            MapError::TerrainOffGrid { index, x, y } =>
+                write!(f, "terrain patch #{index} at {x}:{y} starts outside the grid"),
+        }
+    }
+}
+
+/// Checks a map the way `map_loader` will consume it. An empty result means the map is valid.
+pub fn validate_map(map: &MapFile) -> Vec<MapError> {
+    let mut errors = Vec::new();
+    let grid = &map.tiles;
+    if grid.is_empty() || grid[0].is_empty() {
+        errors.push(MapError::EmptyGrid);
+        return errors;
+    }
+
+    let width = grid[0].len();
+    let mut player = None;
+    let mut goal = None;
+    let mut alien_spawns = Vec::new();
+    for (row, tiles) in grid.iter().enumerate() {
+        if tiles.len() != width {
+            errors.push(MapError::RaggedRow { row, expected: width, found: tiles.len() });
+        }
+        for (col, &code) in tiles.iter().enumerate() {
+            match code {
+                5 => alien_spawns.push((row, col)),
+                9 => goal = Some((row, col)),
+                17 => player = Some((row, col)),
+                c if !VALID_TILE_CODES.contains(&c) => {
+                    errors.push(MapError::UnknownTileCode { row, col, code });
+                }
+                _ => {}
+            }
+        }
+    }
+    if player.is_none() {
+        errors.push(MapError::MissingPlayerSpawn);
+    }
+    if goal.is_none() {
+        errors.push(MapError::MissingGoal);
+    }
+
+    // The BFS assumes a rectangular grid, so only run it on structurally sound maps.
+    let rectangular = grid.iter().all(|tiles| tiles.len() == width);
+    if rectangular && let Some(goal) = goal {
+        for from in player.into_iter().chain(alien_spawns) {
+            if !is_connected(grid, from, goal) {
+                errors.push(MapError::UnreachableGoal { from });
+            }
+        }
+    }
+
+    for (index, dec) in map.decorations.iter().enumerate() {
+        let tile = (dec.y >= 0 && dec.x >= 0)
+            .then(|| grid.get(dec.y as usize).and_then(|tiles| tiles.get(dec.x as usize)))
+            .flatten();
+        match tile {
+            None => errors.push(MapError::DecorationOffGrid { index, x: dec.x, y: dec.y }),
+            Some(0) => errors.push(MapError::DecorationOnVoid { index, x: dec.x, y: dec.y }),
+            Some(_) => {}
+        }
+    }
+
+    for (index, patch) in map.terrain.iter().enumerate() {
+        let on_grid = patch.x >= 0 && patch.y >= 0
+            && (patch.y as usize) < grid.len() && (patch.x as usize) < width;
+        if !on_grid {
+            errors.push(MapError::TerrainOffGrid { index, x: patch.x, y: patch.y });
+        }
+    }
+
+    errors
+}
+
+/// Reads and validates one map file. Generated maps are validated on the generator's output.
+pub fn validate_map_file(path: &Path) -> Vec<MapError> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) => return vec![MapError::Unreadable(e.to_string())],
+    };
+    let map: MapFile = match ron::from_str(&text) {
+        Ok(map) => map,
+        Err(e) => return vec![MapError::Parse(e.to_string())],
+    };
+    if map.generated {
+        validate_map(&generate_map(&map))
+    } else {
+        validate_map(&map)
+    }
+}
+
+/// Validates every `.ron` map in `dir`, sorted by path. `skip` lists file names that are
+/// not maps (e.g. the campaign manifest).
+pub fn validate_maps_dir(dir: &Path, skip: &[&str]) -> Vec<(PathBuf, Vec<MapError>)> {
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "ron"))
+        .filter(|p| !p.file_name().and_then(|n| n.to_str()).is_some_and(|n| skip.contains(&n)))
+        .collect();
+    paths.sort();
+    paths.into_iter()
+        .map(|p| {
+            let errors = validate_map_file(&p);
+            (p, errors)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::components::map_components::{DecorationItem, Terrain, TerrainPatch};
+
+    fn map(tiles: &[&[u8]]) -> MapFile {
+        MapFile {
+            tiles: tiles.iter().map(|row| row.to_vec()).collect(),
+            ..MapFile::default()
+        }
+    }
+
+    fn decoration(x: i32, y: i32) -> DecorationItem {
+        DecorationItem { x, y, model: "packs/nature/Pine.glb".to_string(), rotation_y: 0.0, scale: 1.0 }
+    }
+
+    #[test]
+    fn shipped_maps_are_valid() {
+        let results = validate_maps_dir(Path::new("assets/maps"), &["campaign.ron"]);
+        assert!(!results.is_empty());
+        for (path, errors) in results {
+            assert!(errors.is_empty(), "{}: {errors:?}", path.display());
+        }
+    }
+
+    #[test]
+    fn valid_map_has_no_errors() {
+        assert_eq!(validate_map(&map(&[&[17, 1, 1], &[1, 1, 1], &[5, 1, 9]])), vec![]);
+    }
+
+    #[test]
+    fn ragged_row() {
+        let errors = validate_map(&map(&[&[17, 1, 9], &[5, 1]]));
+        assert!(errors.contains(&MapError::RaggedRow { row: 1, expected: 3, found: 2 }));
+    }
+
+    #[test]
+    fn unknown_tile_code() {
+        let errors = validate_map(&map(&[&[17, 2, 9]]));
+        assert_eq!(errors, vec![MapError::UnknownTileCode { row: 0, col: 1, code: 2 }]);
+    }
+
+    #[test]
+    fn missing_player_spawn() {
+        assert_eq!(validate_map(&map(&[&[5, 1, 9]])), vec![MapError::MissingPlayerSpawn]);
+    }
+
+    #[test]
+    fn missing_goal() {
+        assert_eq!(validate_map(&map(&[&[17, 1, 5]])), vec![MapError::MissingGoal]);
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        let errors = validate_map(&map(&[&[17, 1, 0, 9], &[5, 1, 0, 1]]));
+        assert_eq!(errors, vec![
+            MapError::UnreachableGoal { from: (0, 0) },
+            MapError::UnreachableGoal { from: (1, 0) },
+        ]);
+    }
+
+    #[test]
+    fn decoration_off_grid() {
+        let mut off_grid = map(&[&[17, 1, 9]]);
+        off_grid.decorations = vec![decoration(1, 0), decoration(3, 0), decoration(-1, 0)];
+        assert_eq!(validate_map(&off_grid), vec![
+            MapError::DecorationOffGrid { index: 1, x: 3, y: 0 },
+            MapError::DecorationOffGrid { index: 2, x: -1, y: 0 },
+        ]);
+    }
+
+    #[test]
+    fn empty_grid() {
+        assert_eq!(validate_map(&map(&[])), vec![MapError::EmptyGrid]);
+        assert_eq!(validate_map(&map(&[&[]])), vec![MapError::EmptyGrid]);
+    }
+
+    #[test]
+    fn decoration_on_void() {
+        let mut on_void = map(&[&[17, 0, 9], &[5, 1, 1]]);
+        on_void.decorations = vec![decoration(1, 0), decoration(1, 1)];
+        assert_eq!(validate_map(&on_void), vec![MapError::DecorationOnVoid { index: 0, x: 1, y: 0 }]);
+    }
+
+    #[test]
+    fn terrain_off_grid() {
+        let patch = |x, y| TerrainPatch { x, y, width: 1, height: 1, terrain: Terrain::Mud };
+        let mut off_grid = map(&[&[17, 1, 9]]);
+        off_grid.terrain = vec![patch(1, 0), patch(3, 0), patch(0, -1)];
+        assert_eq!(validate_map(&off_grid), vec![
+            MapError::TerrainOffGrid { index: 1, x: 3, y: 0 },
+            MapError::TerrainOffGrid { index: 2, x: 0, y: -1 },
+        ]);
+    }
+
+    #[test]
+    fn unreadable_file() {
+        let errors = validate_map_file(Path::new("assets/maps/no_such_map.ron"));
+        assert!(matches!(errors.as_slice(), [MapError::Unreadable(_)]), "{errors:?}");
+    }
+
+    #[test]
+    fn malformed_ron() {
+        let path = std::env::temp_dir().join(format!("malformed_map_{}.ron", std::process::id()));
+        std::fs::write(&path, "(tiles: [[17, 1, 9]").unwrap();
+        let errors = validate_map_file(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(errors.as_slice(), [MapError::Parse(_)]), "{errors:?}");
+    }
+}
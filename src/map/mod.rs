@@ -1,2 +1,5 @@
 pub mod map_plugins;
 pub mod map_generator;
+pub mod campaign;
+pub mod map_validator;
+pub mod flow_field;
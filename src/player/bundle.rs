@@ -7,7 +7,7 @@ use crate::control::components::CharacterState;
 use crate::game_state::score_keeper::Score;
 use crate::general::components::{Health};
 use crate::general::components::map_components::CurrentTile;
-use crate::player::components::{AutoAim, Player};
+use crate::player::components::{AutoAim, Launcher, Player};
 
 #[derive(Bundle)]
 pub struct PlayerBundle {
@@ -28,6 +28,7 @@ pub struct PlayerBundle {
     character_state: CharacterState,
     score: Score,
     auto_aim: AutoAim,
+    launcher: Launcher,
 }
 
 impl PlayerBundle {
@@ -58,7 +59,8 @@ impl PlayerBundle {
             current_animation_key: CurrentAnimationKey::new("players".into(), AnimationKey::Idle),
             character_state: CharacterState::default(),
             score: Score::new(),
-            auto_aim: AutoAim(Vec3::Z),
+            auto_aim: AutoAim { direction: Vec3::Z, target: None, aim_point: None },
+            launcher: Launcher::default(),
         }
     }
 }
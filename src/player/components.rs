@@ -20,8 +20,27 @@ pub struct IsObstacle;
 #[derive(Hash, PartialEq, Eq, Clone, Reflect, Component)]
 pub struct ShootingTower;
 
+/// Where the next throw goes.
 #[derive(Component)]
-pub struct AutoAim(pub Vec3);
+pub struct AutoAim {
+    pub direction: Vec3,
+    /// The alien it is locked onto, if any.
+    pub target: Option<Entity>,
+    /// The led point on that alien, for shots that need a landing spot.
+    pub aim_point: Option<Vec3>,
+}
+
+/// Which `ProjectileDef` the player throws.
+#[derive(Component)]
+pub struct Launcher {
+    pub projectile: String,
+}
+
+impl Default for Launcher {
+    fn default() -> Self {
+        Self { projectile: "ball".to_string() }
+    }
+}
 
 /// Marks a player entity whose weapon nodes have been hidden.
 #[derive(Component)]
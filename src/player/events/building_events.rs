@@ -15,6 +15,28 @@ pub struct AddTile(pub (usize, usize));
 #[derive(Message, Clone)]
 pub struct ExitBuildMode(pub Entity);
 
+/// Cycle the targeting mode of the tower under this builder's indicator.
+#[derive(Message, Clone)]
+pub struct CycleTargeting(pub Entity);
+
+/// Upgrade the tower under this builder's indicator to its next tier.
+#[derive(Message, Clone)]
+pub struct UpgradeTower(pub Entity);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ObstacleAction {
+    /// Remove for a partial refund.
+    Sell,
+    /// Restore full health for a cost.
+    Repair,
+    /// Remove, no refund.
+    Demolish,
+}
+
+/// Act on the obstacle under this builder's indicator.
+#[derive(Message, Clone)]
+pub struct ObstacleCommand(pub Entity, pub ObstacleAction);
+
 #[derive(Message, Clone)]
 pub struct ChangeBuildIndicator(pub Entity, pub i32); //negative for back, positive for forward
 
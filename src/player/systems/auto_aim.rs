@@ -1,26 +1,34 @@
 use bevy::math::Vec3Swizzles;
-use bevy::prelude::{Color, Gizmos, GlobalTransform, Query, With};
-use crate::alien::components::general::Alien;
+use bevy::prelude::{Color, Entity, Gizmos, GlobalTransform, Query, Res, With, Without};
+use avian3d::prelude::LinearVelocity;
+use crate::alien::components::general::{Alien, Flyer};
 use crate::constants::PLAYER_FOV_DOT;
 use crate::control::components::{CharacterControl, ControlCommand};
-use crate::player::components::{AutoAim, Player};
+use crate::general::systems::throwing_system::launch_point;
+use crate::player::components::{AutoAim, Launcher, Player};
+use crate::projectiles::aiming::lead_point;
+use crate::projectiles::projectile_defs::ProjectileDefs;
+use crate::settings::resources::GameSettings;
 
 pub fn auto_aim(
-    mut player_query: Query<(&GlobalTransform, &mut AutoAim, &CharacterControl), With<Player>>,
-    alien_query: Query<&GlobalTransform, With<Alien>>,
+    mut player_query: Query<(&GlobalTransform, &mut AutoAim, &Launcher, &CharacterControl), With<Player>>,
+    // Player shots can't reach flyers, so don't aim at them.
+    alien_query: Query<(Entity, &GlobalTransform, &LinearVelocity), (With<Alien>, Without<Flyer>)>,
+    projectile_defs: Res<ProjectileDefs>,
+    settings: Res<GameSettings>,
 ) {
-    for (player_transform, mut auto_aim, character_control) in player_query.iter_mut() {
+    for (player_transform, mut auto_aim, launcher, character_control) in player_query.iter_mut() {
         if character_control.triggers.contains(&ControlCommand::Throw) {
             let forward = player_transform.forward();
             let closest =
                 alien_query
                     .iter()
-                    .filter(|t|
+                    .filter(|(_, t, _)|
                         forward
                             .xz()
                             .dot(
                                 (t.translation().xz() - player_transform.translation().xz()).normalize()) > PLAYER_FOV_DOT)
-                    .min_by(|a, b|
+                    .min_by(|(_, a, _), (_, b, _)|
                         player_transform
                             .translation()
                             .distance(a.translation())
@@ -30,11 +38,19 @@ pub fn auto_aim(
                                     .distance(b.translation())
                             )
                     );
-            if let Some(closest) = closest {
-                auto_aim.0 = (closest.translation() - player_transform.translation()).normalize();
-                auto_aim.0.y = 0.0
+            if let Some((alien, closest, velocity)) = closest {
+                let def = projectile_defs.get(&launcher.projectile);
+                // Lead from where `throwing` will launch the shot, not from the body.
+                let towards_alien = (closest.translation() - player_transform.translation()).with_y(0.0).normalize_or_zero();
+                let launch = launch_point(player_transform.translation(), towards_alien);
+                let aim_point = lead_point(def, launch, closest.translation(), velocity.0, settings.auto_aim_lead);
+                auto_aim.direction = (aim_point - launch).with_y(0.0).normalize_or_zero();
+                auto_aim.target = Some(alien);
+                auto_aim.aim_point = Some(aim_point);
             } else {
-                auto_aim.0 = *player_transform.forward();
+                auto_aim.direction = *player_transform.forward();
+                auto_aim.target = None;
+                auto_aim.aim_point = None;
             }
         }
     }
@@ -52,7 +68,7 @@ pub fn debug_gizmos(
         );
         gizmos.line(
             player_transform.translation(),
-            player_transform.translation() + auto_aim.0 * 10.0,
+            player_transform.translation() + auto_aim.direction * 10.0,
             Color::srgb(1.0, 0.0, 0.0),
         );
     }
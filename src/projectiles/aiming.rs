@@ -0,0 +1,93 @@
+use bevy::math::Vec3;
+use crate::projectiles::projectile_defs::{ProjectileDef, Trajectory};
+
+/// Seconds until a projectile at `speed` can meet a target that is `offset` away and moving at
+/// `target_velocity`, or `None` when it can never catch up. Solves |offset + v·t| = speed·t.
+pub fn intercept_time(offset: Vec3, target_velocity: Vec3, speed: f32) -> Option<f32> {
+    let a = target_velocity.length_squared() - speed * speed;
+    let b = 2.0 * offset.dot(target_velocity);
+    let c = offset.length_squared();
+    if a.abs() < f32::EPSILON {
+        // Same speed as the target: only one root.
+        return (b < 0.0).then(|| -c / b);
+    }
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let root = discriminant.sqrt();
+    [(-b - root) / (2.0 * a), (-b + root) / (2.0 * a)]
+        .into_iter()
+        .filter(|t| *t > 0.0)
+        .min_by(f32::total_cmp)
+}
+
+/// Where to aim `def` from `origin` to hit a target at `target` moving at `target_velocity`.
+/// `strength` blends from the target's current position (0) to the full intercept point (1).
+/// Ballistic shots fly at `speed` horizontally, so they lead on the ground plane only.
+pub fn lead_point(def: &ProjectileDef, origin: Vec3, target: Vec3, target_velocity: Vec3, strength: f32) -> Vec3 {
+    if strength <= 0.0 {
+        return target;
+    }
+    let (offset, velocity) = match def.trajectory {
+        Trajectory::Ballistic => (
+            (target - origin).with_y(0.0),
+            target_velocity.with_y(0.0),
+        ),
+        _ => (target - origin, target_velocity),
+    };
+    match intercept_time(offset, velocity, def.speed) {
+        Some(time) => target + velocity * time * strength.min(1.0),
+        None => target,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn def(trajectory: &str) -> ProjectileDef {
+        ron::from_str(&format!("(speed: 5.0, damage: 1, trajectory: {trajectory})")).unwrap()
+    }
+
+    fn assert_near(a: Vec3, b: Vec3) {
+        assert!(a.distance(b) < 1e-4, "{a} != {b}");
+    }
+
+    #[test]
+    fn stationary_target_is_hit_where_it_stands() {
+        assert_eq!(intercept_time(Vec3::new(10.0, 0.0, 0.0), Vec3::ZERO, 5.0), Some(2.0));
+        let target = Vec3::new(10.0, 0.0, 0.0);
+        assert_near(lead_point(&def("Straight"), Vec3::ZERO, target, Vec3::ZERO, 1.0), target);
+    }
+
+    #[test]
+    fn receding_target_that_outruns_the_shot_cannot_be_caught() {
+        let offset = Vec3::new(10.0, 0.0, 0.0);
+        assert_eq!(intercept_time(offset, Vec3::new(6.0, 0.0, 0.0), 5.0), None);
+        assert_eq!(intercept_time(offset, Vec3::new(5.0, 0.0, 0.0), 5.0), None);
+        // Without an intercept the shot goes at the target as it is.
+        assert_near(lead_point(&def("Straight"), Vec3::ZERO, offset, Vec3::new(6.0, 0.0, 0.0), 1.0), offset);
+    }
+
+    #[test]
+    fn straight_lead_meets_a_crossing_target() {
+        // |(10, 0, 3t)| = 5t gives t = 2.5.
+        let target = Vec3::new(10.0, 0.0, 0.0);
+        let velocity = Vec3::new(0.0, 0.0, 3.0);
+        assert_near(lead_point(&def("Straight"), Vec3::ZERO, target, velocity, 1.0), Vec3::new(10.0, 0.0, 7.5));
+        assert_near(lead_point(&def("Straight"), Vec3::ZERO, target, velocity, 0.5), Vec3::new(10.0, 0.0, 3.75));
+        assert_near(lead_point(&def("Straight"), Vec3::ZERO, target, velocity, 0.0), target);
+    }
+
+    #[test]
+    fn ballistic_lead_ignores_height() {
+        let target = Vec3::new(10.0, 2.0, 0.0);
+        let velocity = Vec3::new(0.0, 1.0, 3.0);
+        let ballistic = lead_point(&def("Ballistic"), Vec3::ZERO, target, velocity, 1.0);
+        assert_near(ballistic, Vec3::new(10.0, 2.0, 7.5));
+        let straight = lead_point(&def("Straight"), Vec3::ZERO, target, velocity, 1.0);
+        assert!(straight.y > target.y);
+        assert!(straight.z > ballistic.z);
+    }
+}
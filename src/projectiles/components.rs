@@ -0,0 +1,38 @@
+use bevy::prelude::{Component, Entity};
+use crate::status_effects::components::StatusEffect;
+
+/// A projectile in flight. `shooter` is credited with hits and kills.
+#[derive(Component)]
+pub struct Projectile {
+    pub shooter: Entity,
+    pub bounces: u32,
+    pub max_bounces: u32,
+    pub damage_bounces: u32,
+    pub can_score: bool,
+    pub damage: i32,
+    pub splash_radius: f32,
+    /// Aliens it may still pass through.
+    pub pierce_left: u32,
+    pub piercing: bool,
+    pub effects: Vec<StatusEffect>,
+    /// Fired by an alien; see `FireProjectile::hostile`.
+    pub hostile: bool,
+    pub anti_air: bool,
+    /// Seconds left before it is despawned.
+    pub lifetime: f32,
+}
+
+impl Projectile {
+    /// Still on one of its first `damage_bounces` contacts; `bounces` counts the ones before this.
+    pub fn can_damage(&self) -> bool {
+        self.bounces < self.damage_bounces
+    }
+}
+
+/// Steers the projectile's velocity toward `target`.
+#[derive(Component)]
+pub struct Homing {
+    pub target: Entity,
+    /// Radians per second.
+    pub turn_rate: f32,
+}
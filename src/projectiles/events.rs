@@ -0,0 +1,23 @@
+use bevy::math::Vec3;
+use bevy::prelude::{Entity, Message};
+
+/// The one way to launch a projectile, for players, towers and aliens alike.
+#[derive(Message, Clone)]
+pub struct FireProjectile {
+    /// Key of a `ProjectileDef`.
+    pub projectile: String,
+    pub shooter: Entity,
+    pub origin: Vec3,
+    pub direction: Vec3,
+    /// What is being shot at, if anything. Homing and ballistic shots need it.
+    pub target: Option<Entity>,
+    /// Where a ballistic shot should come down, usually a led intercept point.
+    /// The target's current position when left out.
+    pub aim_point: Option<Vec3>,
+    /// Overrides the definition's damage.
+    pub damage: Option<i32>,
+    /// Fired by an alien: hurts players and obstacles and passes through aliens.
+    pub hostile: bool,
+    /// Can hit flyers.
+    pub anti_air: bool,
+}
@@ -0,0 +1,6 @@
+pub(crate) mod aiming;
+pub(crate) mod components;
+pub(crate) mod events;
+pub(crate) mod projectile_defs;
+pub(crate) mod projectile_plugin;
+pub(crate) mod systems;
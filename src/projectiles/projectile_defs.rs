@@ -0,0 +1,117 @@
+use std::path::Path;
+use bevy::prelude::Resource;
+use serde::Deserialize;
+use crate::general::ron_dir::load_ron_dir;
+use crate::status_effects::components::StatusEffect;
+
+pub const PROJECTILES_DIR: &str = "assets/projectiles";
+
+/// How a projectile leaves the launcher.
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Trajectory {
+    /// Launched along the aim and left to gravity.
+    #[default]
+    Thrown,
+    /// Flies in a straight line, no gravity.
+    Straight,
+    /// Lobbed so that gravity brings it down on the target.
+    Ballistic,
+}
+
+/// One kind of projectile, read from `assets/projectiles/<key>.ron`. Behaviours compose:
+/// a ballistic shot can also splash, a straight one can home and pierce.
+#[derive(Deserialize, Clone, Debug)]
+pub struct ProjectileDef {
+    /// File stem; filled in by `ProjectileDefs::load`.
+    #[serde(skip)]
+    pub key: String,
+    /// Scene path relative to `assets/`; the ball scene when left out.
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default = "default_radius")]
+    pub radius: f32,
+    pub speed: f32,
+    /// Damage when the launcher doesn't set its own (towers do).
+    pub damage: i32,
+    #[serde(default)]
+    pub trajectory: Trajectory,
+    /// Turn rate in degrees per second toward the target; 0 disables homing.
+    #[serde(default)]
+    pub homing: f32,
+    /// Aliens within this radius of the impact also take the damage; 0 disables splash.
+    #[serde(default)]
+    pub splash_radius: f32,
+    /// How many aliens it passes through before stopping. Piercing projectiles fly as sensors
+    /// and end at the first wall or floor they touch.
+    #[serde(default)]
+    pub pierce: u32,
+    /// Despawns after this many contacts.
+    #[serde(default = "default_max_bounces")]
+    pub max_bounces: u32,
+    /// How many contacts it hurts on, counting the first; later bounces are harmless.
+    #[serde(default = "default_damage_bounces")]
+    pub damage_bounces: u32,
+    /// Put on everything it damages, e.g. a burn or a knockback.
+    #[serde(default)]
+    pub effects: Vec<StatusEffect>,
+    /// Seconds before it is despawned, hit or miss.
+    #[serde(default = "default_lifetime")]
+    pub lifetime: f32,
+}
+
+fn default_radius() -> f32 { 1.0 / 16.0 }
+fn default_max_bounces() -> u32 { 5 }
+fn default_damage_bounces() -> u32 { 2 }
+fn default_lifetime() -> f32 { 5.0 }
+
+/// Every projectile kind, by key.
+#[derive(Resource, Debug)]
+pub struct ProjectileDefs {
+    pub defs: Vec<ProjectileDef>,
+}
+
+impl ProjectileDefs {
+    /// Reads every `*.ron` in `PROJECTILES_DIR`, always keeping a built-in `ball`
+    /// so players and towers have something to fire.
+    pub fn load() -> Self {
+        let mut defs: Vec<ProjectileDef> = load_ron_dir(Path::new(PROJECTILES_DIR))
+            .into_iter()
+            .map(|(key, def)| ProjectileDef { key, ..def })
+            .collect();
+        if !defs.iter().any(|def| def.key == "ball") {
+            defs.push(Self::ball());
+        }
+        Self { defs }
+    }
+
+    fn ball() -> ProjectileDef {
+        ProjectileDef {
+            key: "ball".to_string(),
+            model: None,
+            radius: default_radius(),
+            speed: 12.0,
+            damage: 10,
+            trajectory: Trajectory::Thrown,
+            homing: 0.0,
+            splash_radius: 0.0,
+            pierce: 0,
+            max_bounces: default_max_bounces(),
+            damage_bounces: default_damage_bounces(),
+            effects: Vec::new(),
+            lifetime: default_lifetime(),
+        }
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.defs.iter().any(|def| def.key == key)
+    }
+
+    /// The definition for `key`, or the ball if there is none. `check_projectile_keys`
+    /// warns about unknown keys at startup.
+    pub fn get(&self, key: &str) -> &ProjectileDef {
+        self.defs.iter()
+            .find(|def| def.key == key)
+            .or_else(|| self.defs.iter().find(|def| def.key == "ball"))
+            .expect("ProjectileDefs always holds a ball")
+    }
+}
@@ -0,0 +1,25 @@
+use bevy::app::{App, Plugin, Startup, Update};
+use bevy::prelude::{in_state, IntoScheduleConfigs};
+use crate::game_state::GameState;
+use crate::projectiles::events::FireProjectile;
+use crate::projectiles::projectile_defs::ProjectileDefs;
+use crate::projectiles::systems::{check_projectile_keys, homing_projectile_system, projectile_lifetime_system, spawn_projectile_system};
+
+pub struct ProjectilePlugin;
+
+impl Plugin for ProjectilePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_message::<FireProjectile>()
+            .insert_resource(ProjectileDefs::load())
+            .add_systems(Startup, check_projectile_keys)
+            .add_systems(
+                Update,
+                (
+                    spawn_projectile_system,
+                    homing_projectile_system,
+                    projectile_lifetime_system,
+                ).run_if(in_state(GameState::InGame)),
+            );
+    }
+}
@@ -0,0 +1,165 @@
+use bevy::asset::AssetServer;
+use bevy::math::{Quat, Vec3, Vec3Swizzles};
+use bevy::log::warn;
+use bevy::prelude::{Commands, Entity, MessageReader, Name, Query, Res, Transform, With};
+use bevy::scene::SceneRoot;
+use bevy::time::Time;
+use avian3d::prelude::{Collider, CollisionLayers, GravityScale, Gravity, LinearVelocity, Position, RigidBody, Sensor};
+use bevy_wind_waker_shader::WindWakerShaderBuilder;
+use crate::alien::alien_defs::AlienDefs;
+use crate::assets::assets_plugin::GameAssets;
+use crate::general::components::CollisionLayer;
+use crate::projectiles::components::{Homing, Projectile};
+use crate::projectiles::events::FireProjectile;
+use crate::projectiles::projectile_defs::{ProjectileDefs, Trajectory};
+use crate::towers::tower_defs::TowerDefs;
+
+/// Spawns every `FireProjectile` according to its `ProjectileDef`.
+pub fn spawn_projectile_system(
+    mut fire_projectile_mr: MessageReader<FireProjectile>,
+    mut commands: Commands,
+    projectile_defs: Res<ProjectileDefs>,
+    target_query: Query<&Position>,
+    gravity: Res<Gravity>,
+    game_assets: Res<GameAssets>,
+    asset_server: Res<AssetServer>,
+) {
+    for fire in fire_projectile_mr.read() {
+        let def = projectile_defs.get(&fire.projectile);
+        let target_position = fire.aim_point
+            .or_else(|| fire.target.and_then(|target| target_query.get(target).ok()).map(|p| p.0));
+
+        let velocity = match (def.trajectory, target_position) {
+            // Time of flight from horizontal speed, then whatever vertical launch makes
+            // gravity land it on the target: v = delta / t - g * t / 2.
+            (Trajectory::Ballistic, Some(target)) => {
+                let delta = target - fire.origin;
+                let time = (delta.xz().length() / def.speed).max(0.1);
+                delta / time - gravity.0 * time / 2.0
+            }
+            _ => fire.direction.normalize_or_zero() * def.speed,
+        };
+        let scene = match &def.model {
+            Some(model) => asset_server.load(model.clone()),
+            None => game_assets.ball_scene.clone(),
+        };
+
+        let mut projectile = commands.spawn((
+            Name::from(def.key.clone()),
+            Projectile {
+                shooter: fire.shooter,
+                bounces: 0,
+                max_bounces: def.max_bounces,
+                damage_bounces: def.damage_bounces,
+                can_score: true,
+                damage: fire.damage.unwrap_or(def.damage),
+                splash_radius: def.splash_radius,
+                pierce_left: def.pierce,
+                piercing: def.pierce > 0,
+                effects: def.effects.clone(),
+                hostile: fire.hostile,
+                anti_air: fire.anti_air,
+                lifetime: def.lifetime,
+            },
+            SceneRoot(scene),
+            Transform::from_translation(fire.origin),
+            RigidBody::Dynamic,
+            Collider::sphere(def.radius),
+            WindWakerShaderBuilder::default().build(),
+            LinearVelocity(velocity),
+            projectile_collision_layers(fire),
+        ));
+        if def.trajectory == Trajectory::Straight {
+            projectile.insert(GravityScale(0.0));
+        }
+        if def.pierce > 0 {
+            projectile.insert(Sensor);
+        }
+        if def.homing > 0.0 && let Some(target) = fire.target {
+            projectile.insert(Homing {
+                target,
+                turn_rate: def.homing.to_radians(),
+            });
+        }
+    }
+}
+
+/// Alien shots fly through aliens and hit players; everyone else's the other way round.
+/// Only anti-air shots touch flyers.
+fn projectile_collision_layers(fire: &FireProjectile) -> CollisionLayers {
+    let mut mask = vec![
+        CollisionLayer::Impassable,
+        CollisionLayer::Floor,
+        CollisionLayer::Player,
+        CollisionLayer::AlienSpawnPoint,
+        CollisionLayer::AlienGoal,
+    ];
+    if !fire.hostile {
+        mask.push(CollisionLayer::Alien);
+    }
+    if fire.anti_air {
+        mask.push(CollisionLayer::Flyer);
+    }
+    CollisionLayers::new([CollisionLayer::Ball], mask)
+}
+
+/// Despawns projectiles that outlived their definition's `lifetime`, such as straight shots
+/// that missed and will never land.
+pub fn projectile_lifetime_system(
+    mut commands: Commands,
+    mut projectile_query: Query<(Entity, &mut Projectile)>,
+    time: Res<Time>,
+) {
+    for (entity, mut projectile) in projectile_query.iter_mut() {
+        projectile.lifetime -= time.delta_secs();
+        if projectile.lifetime <= 0.0 {
+            // The collision system may be despawning it this frame too.
+            commands.entity(entity).try_despawn();
+        }
+    }
+}
+
+/// Warns about tower weapons and ranged aliens whose projectile key has no definition. They fire balls.
+pub fn check_projectile_keys(
+    projectile_defs: Res<ProjectileDefs>,
+    tower_defs: Res<TowerDefs>,
+    alien_defs: Res<AlienDefs>,
+) {
+    for def in &tower_defs.defs {
+        if let Some(weapon) = &def.weapon
+            && !projectile_defs.contains(&weapon.projectile) {
+            warn!("towers/{}.ron: unknown projectile {:?}, firing balls", def.key, weapon.projectile);
+        }
+    }
+    for def in &alien_defs.defs {
+        if let Some(ranged) = &def.ranged
+            && !projectile_defs.contains(&ranged.projectile) {
+            warn!("aliens/{}.ron: unknown projectile {:?}, firing balls", def.key, ranged.projectile);
+        }
+    }
+}
+
+/// Turns homing projectiles toward their target at a limited rate, keeping their speed.
+/// Once the target is gone they fly on unguided.
+pub fn homing_projectile_system(
+    mut commands: Commands,
+    mut projectile_query: Query<(Entity, &Position, &mut LinearVelocity, &Homing), With<Projectile>>,
+    target_query: Query<&Position>,
+    time: Res<Time>,
+) {
+    for (entity, position, mut velocity, homing) in projectile_query.iter_mut() {
+        let Ok(target) = target_query.get(homing.target) else {
+            commands.entity(entity).remove::<Homing>();
+            continue;
+        };
+        let wanted = (target.0 - position.0).normalize_or_zero();
+        let current = velocity.0.normalize_or_zero();
+        if wanted == Vec3::ZERO || current == Vec3::ZERO {
+            continue;
+        }
+        let angle = current.angle_between(wanted);
+        let step = (homing.turn_rate * time.delta_secs() / angle.max(f32::EPSILON)).min(1.0);
+        let turn = Quat::IDENTITY.slerp(Quat::from_rotation_arc(current, wanted), step);
+        velocity.0 = turn * velocity.0;
+    }
+}
@@ -44,6 +44,9 @@ pub struct GameSettings {
     pub persp_near: f32,
     #[serde(default = "default_persp_far")]
     pub persp_far: f32,
+    /// How far auto-aim leads a moving alien: 0 aims at it, 1 at the full intercept point.
+    #[serde(default = "default_auto_aim_lead")]
+    pub auto_aim_lead: f32,
 }
 
 fn default_projection() -> ProjectionMode { ProjectionMode::Orthographic }
@@ -58,6 +61,7 @@ fn default_ortho_viewport_height() -> f32 { 2.0 }
 fn default_persp_fov()  -> f32 { 60.0 }
 fn default_persp_near() -> f32 { 0.1 }
 fn default_persp_far() -> f32 { 1000.0 }
+fn default_auto_aim_lead() -> f32 { 1.0 }
 
 impl Default for GameSettings {
     fn default() -> Self {
@@ -74,6 +78,7 @@ impl Default for GameSettings {
             persp_fov: default_persp_fov(),
             persp_near: default_persp_near(),
             persp_far: default_persp_far(),
+            auto_aim_lead: default_auto_aim_lead(),
         }
     }
 }
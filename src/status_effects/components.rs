@@ -0,0 +1,309 @@
+use bevy::color::Color;
+use bevy::math::Vec3;
+use bevy::prelude::{Component, Entity};
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StatusKind {
+    Slow,
+    Burn,
+    Stun,
+    Knockback,
+}
+
+impl StatusKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            StatusKind::Slow => "SLOW",
+            StatusKind::Burn => "BURN",
+            StatusKind::Stun => "STUN",
+            StatusKind::Knockback => "KNOCK",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            StatusKind::Slow => Color::srgb(0.4, 0.7, 1.0),
+            StatusKind::Burn => Color::srgb(1.0, 0.5, 0.1),
+            StatusKind::Stun => Color::srgb(1.0, 1.0, 0.3),
+            StatusKind::Knockback => Color::srgb(0.9, 0.9, 0.9),
+        }
+    }
+}
+
+/// What happens when an effect lands on a target that already has one of the same kind.
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Stacking {
+    /// Restart the duration; the newer values win.
+    #[default]
+    Refresh,
+    /// Add the new duration to what is left.
+    Extend,
+    /// Add a stack, up to `max_stacks`, and restart the duration. Every stack counts in full.
+    Stack,
+    /// Keep whichever slows or hurts more, restarting the duration if it is the new one.
+    Strongest,
+}
+
+/// A time-bounded modifier, as written in tower and projectile files. Each kind means one
+/// thing: a Slow slows, a Burn hurts over time, a Stun stops the target moving and attacking,
+/// and a Knockback pushes. Files whose values don't fit their kind fail to load.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(try_from = "StatusEffectFile")]
+pub struct StatusEffect {
+    pub kind: StatusKind,
+    /// Seconds.
+    pub duration: f32,
+    pub stacking: Stacking,
+    pub max_stacks: u32,
+    /// Multiplier on movement and turning; 0 stops the target. Always 0 for a Stun.
+    pub speed_multiplier: f32,
+    /// Health taken per second.
+    pub damage_per_second: f32,
+    /// Speed at which the target is pushed away from the source.
+    pub knockback: f32,
+}
+
+/// `StatusEffect` as written, before it is checked against its kind.
+#[derive(Deserialize)]
+struct StatusEffectFile {
+    kind: StatusKind,
+    duration: f32,
+    #[serde(default)]
+    stacking: Stacking,
+    #[serde(default = "default_max_stacks")]
+    max_stacks: u32,
+    #[serde(default = "default_speed_multiplier")]
+    speed_multiplier: f32,
+    #[serde(default)]
+    damage_per_second: f32,
+    #[serde(default)]
+    knockback: f32,
+}
+
+fn default_max_stacks() -> u32 { 1 }
+fn default_speed_multiplier() -> f32 { 1.0 }
+
+impl TryFrom<StatusEffectFile> for StatusEffect {
+    type Error = String;
+
+    fn try_from(file: StatusEffectFile) -> Result<Self, Self::Error> {
+        if file.duration <= 0.0 {
+            return Err(format!("{:?} needs a duration above 0", file.kind));
+        }
+        let speed_multiplier = match file.kind {
+            StatusKind::Slow if !(0.0..1.0).contains(&file.speed_multiplier) =>
+                return Err("Slow needs a speed_multiplier from 0 up to, not including, 1".to_string()),
+            StatusKind::Burn if file.damage_per_second <= 0.0 =>
+                return Err("Burn needs a damage_per_second above 0".to_string()),
+            StatusKind::Knockback if file.knockback <= 0.0 =>
+                return Err("Knockback needs a knockback above 0".to_string()),
+            StatusKind::Stun => 0.0,
+            _ => file.speed_multiplier,
+        };
+        Ok(Self {
+            kind: file.kind,
+            duration: file.duration,
+            stacking: file.stacking,
+            max_stacks: file.max_stacks,
+            speed_multiplier,
+            damage_per_second: file.damage_per_second,
+            knockback: file.knockback,
+        })
+    }
+}
+
+impl StatusEffect {
+    /// Used by `Stacking::Strongest` to compare two effects of the same kind.
+    fn strength(&self) -> f32 {
+        (1.0 - self.speed_multiplier) + self.damage_per_second + self.knockback
+    }
+}
+
+/// One effect currently running on a target.
+#[derive(Clone, Debug)]
+pub struct ActiveEffect {
+    pub effect: StatusEffect,
+    /// Credited with kills by tick damage.
+    pub source: Entity,
+    pub remaining: f32,
+    pub stacks: u32,
+    /// Knockback velocity, fixed when the effect lands.
+    pub push: Vec3,
+    /// Tick damage not yet taken off whole health points.
+    pub pending_damage: f32,
+}
+
+/// Everything affecting one character. Movement and the health bar read it; only
+/// `apply_status_system` and `tick_status_effects` change it.
+#[derive(Component, Default, Debug)]
+pub struct StatusEffects {
+    pub effects: Vec<ActiveEffect>,
+}
+
+impl StatusEffects {
+    /// Adds `effect` following its stacking rule. `direction` points from the source to the
+    /// target and is only used by knockback.
+    pub fn apply(&mut self, effect: &StatusEffect, source: Entity, direction: Vec3) {
+        let push = direction.with_y(0.0).normalize_or_zero() * effect.knockback;
+        let Some(active) = self.effects.iter_mut().find(|active| active.effect.kind == effect.kind) else {
+            self.effects.push(ActiveEffect {
+                effect: effect.clone(),
+                source,
+                remaining: effect.duration,
+                stacks: 1,
+                push,
+                pending_damage: 0.0,
+            });
+            return;
+        };
+        match effect.stacking {
+            Stacking::Refresh => {
+                active.effect = effect.clone();
+                active.remaining = effect.duration;
+                active.source = source;
+                active.push = push;
+            }
+            Stacking::Extend => active.remaining += effect.duration,
+            Stacking::Stack => {
+                active.stacks = (active.stacks + 1).min(effect.max_stacks.max(1));
+                active.remaining = effect.duration;
+                active.source = source;
+            }
+            Stacking::Strongest => {
+                if effect.strength() >= active.effect.strength() {
+                    active.effect = effect.clone();
+                    active.remaining = effect.duration;
+                    active.source = source;
+                    active.push = push;
+                }
+            }
+        }
+    }
+
+    /// Combined multiplier on movement and turning.
+    pub fn speed_multiplier(&self) -> f32 {
+        self.effects.iter()
+            .map(|active| active.effect.speed_multiplier.max(0.0).powi(active.stacks as i32))
+            .product()
+    }
+
+    /// Whether a Stun is running, which also keeps the target from attacking.
+    pub fn stunned(&self) -> bool {
+        self.effects.iter().any(|active| active.effect.kind == StatusKind::Stun)
+    }
+
+    /// Sum of the knockback velocities still running.
+    pub fn push(&self) -> Vec3 {
+        self.effects.iter().map(|active| active.push).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::world::World;
+    use super::*;
+
+    fn effect(text: &str) -> StatusEffect {
+        ron::from_str(text).unwrap()
+    }
+
+    fn sources() -> (Entity, Entity) {
+        let mut world = World::new();
+        (world.spawn_empty().id(), world.spawn_empty().id())
+    }
+
+    #[test]
+    fn refresh_restarts_and_takes_the_newer_values() {
+        let (first, second) = sources();
+        let mut effects = StatusEffects::default();
+        effects.apply(&effect("(kind: Slow, duration: 2.0, speed_multiplier: 0.5)"), first, Vec3::X);
+        effects.effects[0].remaining = 0.5;
+        effects.apply(&effect("(kind: Slow, duration: 1.0, speed_multiplier: 0.8)"), second, Vec3::X);
+        assert_eq!(effects.effects.len(), 1);
+        let active = &effects.effects[0];
+        assert_eq!(active.remaining, 1.0);
+        assert_eq!(active.effect.speed_multiplier, 0.8);
+        assert_eq!(active.source, second);
+        assert_eq!(active.stacks, 1);
+    }
+
+    #[test]
+    fn extend_adds_to_what_is_left() {
+        let (source, _) = sources();
+        let mut effects = StatusEffects::default();
+        let slow = effect("(kind: Slow, duration: 2.0, stacking: Extend, speed_multiplier: 0.5)");
+        effects.apply(&slow, source, Vec3::X);
+        effects.effects[0].remaining = 0.5;
+        effects.apply(&slow, source, Vec3::X);
+        assert_eq!(effects.effects[0].remaining, 2.5);
+        assert_eq!(effects.effects[0].stacks, 1);
+    }
+
+    #[test]
+    fn stack_counts_up_to_max_stacks_and_restarts() {
+        let (source, _) = sources();
+        let mut effects = StatusEffects::default();
+        let burn = effect("(kind: Burn, duration: 3.0, stacking: Stack, max_stacks: 2, damage_per_second: 4.0)");
+        for _ in 0..3 {
+            effects.effects.iter_mut().for_each(|active| active.remaining = 1.0);
+            effects.apply(&burn, source, Vec3::X);
+        }
+        assert_eq!(effects.effects.len(), 1);
+        assert_eq!(effects.effects[0].stacks, 2);
+        assert_eq!(effects.effects[0].remaining, 3.0);
+    }
+
+    #[test]
+    fn stacked_slows_multiply() {
+        let (source, _) = sources();
+        let mut effects = StatusEffects::default();
+        let slow = effect("(kind: Slow, duration: 1.0, stacking: Stack, max_stacks: 3, speed_multiplier: 0.5)");
+        effects.apply(&slow, source, Vec3::X);
+        effects.apply(&slow, source, Vec3::X);
+        assert_eq!(effects.speed_multiplier(), 0.25);
+    }
+
+    #[test]
+    fn strongest_keeps_the_stronger_effect() {
+        let (first, second) = sources();
+        let mut effects = StatusEffects::default();
+        effects.apply(&effect("(kind: Slow, duration: 2.0, stacking: Strongest, speed_multiplier: 0.3)"), first, Vec3::X);
+        effects.apply(&effect("(kind: Slow, duration: 5.0, stacking: Strongest, speed_multiplier: 0.8)"), second, Vec3::X);
+        assert_eq!(effects.effects[0].effect.speed_multiplier, 0.3);
+        assert_eq!(effects.effects[0].remaining, 2.0);
+        assert_eq!(effects.effects[0].source, first);
+
+        effects.apply(&effect("(kind: Slow, duration: 4.0, stacking: Strongest, speed_multiplier: 0.1)"), second, Vec3::X);
+        assert_eq!(effects.effects[0].effect.speed_multiplier, 0.1);
+        assert_eq!(effects.effects[0].remaining, 4.0);
+        assert_eq!(effects.effects[0].source, second);
+    }
+
+    #[test]
+    fn different_kinds_run_side_by_side() {
+        let (source, _) = sources();
+        let mut effects = StatusEffects::default();
+        effects.apply(&effect("(kind: Slow, duration: 1.0, speed_multiplier: 0.5)"), source, Vec3::X);
+        effects.apply(&effect("(kind: Knockback, duration: 0.5, knockback: 2.0)"), source, Vec3::new(0.0, 1.0, 3.0));
+        assert_eq!(effects.effects.len(), 2);
+        assert_eq!(effects.push(), Vec3::new(0.0, 0.0, 2.0));
+    }
+
+    #[test]
+    fn stun_stops_the_target() {
+        let (source, _) = sources();
+        let mut effects = StatusEffects::default();
+        effects.apply(&effect("(kind: Stun, duration: 1.0)"), source, Vec3::X);
+        assert!(effects.stunned());
+        assert_eq!(effects.speed_multiplier(), 0.0);
+    }
+
+    #[test]
+    fn effects_that_do_nothing_are_rejected() {
+        assert!(ron::from_str::<StatusEffect>("(kind: Knockback, duration: 1.0)").is_err());
+        assert!(ron::from_str::<StatusEffect>("(kind: Slow, duration: 1.0)").is_err());
+        assert!(ron::from_str::<StatusEffect>("(kind: Burn, duration: 1.0)").is_err());
+        assert!(ron::from_str::<StatusEffect>("(kind: Stun, duration: 0.0)").is_err());
+    }
+}
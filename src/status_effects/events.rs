@@ -0,0 +1,13 @@
+use bevy::math::Vec3;
+use bevy::prelude::{Entity, Message};
+use crate::status_effects::components::StatusEffect;
+
+/// Puts `effect` on `target`. Anything with `StatusEffects` can receive it.
+#[derive(Message, Clone)]
+pub struct ApplyStatus {
+    pub target: Entity,
+    pub source: Entity,
+    pub effect: StatusEffect,
+    /// From the source toward the target; sets the knockback direction.
+    pub direction: Vec3,
+}
@@ -0,0 +1,4 @@
+pub(crate) mod components;
+pub(crate) mod events;
+pub(crate) mod status_effect_plugin;
+pub(crate) mod systems;
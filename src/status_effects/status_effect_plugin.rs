@@ -0,0 +1,22 @@
+use bevy::app::{App, Plugin, Update};
+use bevy::prelude::{in_state, IntoScheduleConfigs};
+use crate::game_state::GameState;
+use crate::status_effects::events::ApplyStatus;
+use crate::status_effects::systems::{apply_status_system, knockback_system, tick_status_effects};
+
+pub struct StatusEffectPlugin;
+
+impl Plugin for StatusEffectPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_message::<ApplyStatus>()
+            .add_systems(
+                Update,
+                (
+                    apply_status_system,
+                    tick_status_effects,
+                    knockback_system,
+                ).chain().run_if(in_state(GameState::InGame)),
+            );
+    }
+}
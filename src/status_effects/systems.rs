@@ -0,0 +1,67 @@
+use bevy::math::Vec3;
+use bevy::prelude::{Entity, Has, MessageReader, MessageWriter, Query, Res, ResMut, Without};
+use bevy::time::Time;
+use avian3d::prelude::LinearVelocity;
+use crate::alien::components::general::{Alien, AlienCounter, Bounty, Flyer};
+use crate::game_state::score_keeper::GameTrackingEvent;
+use crate::general::components::Health;
+use crate::status_effects::components::StatusEffects;
+use crate::status_effects::events::ApplyStatus;
+
+pub fn apply_status_system(
+    mut apply_status_mr: MessageReader<ApplyStatus>,
+    mut target_query: Query<&mut StatusEffects>,
+) {
+    for apply in apply_status_mr.read() {
+        if let Ok(mut status_effects) = target_query.get_mut(apply.target) {
+            status_effects.apply(&apply.effect, apply.source, apply.direction);
+        }
+    }
+}
+
+/// Counts effects down, deals their tick damage and drops the ones that ran out.
+pub fn tick_status_effects(
+    mut target_query: Query<(&mut StatusEffects, &mut Health, Has<Alien>, Option<&Bounty>)>,
+    mut alien_counter: ResMut<AlienCounter>,
+    mut game_mw: MessageWriter<GameTrackingEvent>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_secs();
+    for (mut status_effects, mut health, is_alien, bounty) in target_query.iter_mut() {
+        if status_effects.effects.is_empty() {
+            continue;
+        }
+        let mut killed_by: Option<Entity> = None;
+        for active in status_effects.effects.iter_mut() {
+            let ticking = delta.min(active.remaining.max(0.0));
+            active.remaining -= delta;
+            active.pending_damage += active.effect.damage_per_second * active.stacks as f32 * ticking;
+            let whole = active.pending_damage.floor();
+            if whole >= 1.0 {
+                active.pending_damage -= whole;
+                if health.take_damage(whole as i32) {
+                    killed_by = Some(active.source);
+                }
+            }
+        }
+        status_effects.effects.retain(|active| active.remaining > 0.0);
+
+        if let Some(source) = killed_by && is_alien {
+            game_mw.write(GameTrackingEvent::AlienKilled { by: source, bounty: bounty.map_or(0, |bounty| bounty.0) });
+            alien_counter.count -= 1;
+        }
+    }
+}
+
+/// Carries walkers along with the knockbacks still running on them. Flyers set their own velocity.
+pub fn knockback_system(
+    mut target_query: Query<(&StatusEffects, &mut LinearVelocity), Without<Flyer>>,
+) {
+    for (status_effects, mut velocity) in target_query.iter_mut() {
+        let push = status_effects.push();
+        if push != Vec3::ZERO {
+            velocity.x = push.x;
+            velocity.z = push.z;
+        }
+    }
+}
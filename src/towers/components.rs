@@ -1,22 +1,84 @@
 use bevy::prelude::Component;
+use serde::Deserialize;
 use crate::general::components::map_components::CoolDown;
+use crate::status_effects::components::StatusEffect;
+use crate::towers::tower_defs::{TowerTrap, TowerWeapon};
 
 #[derive(Component)]
 pub struct TowerSensor {}
 
+/// The field sensor of an aura tower; every alien it touches gets `effect`.
+#[derive(Component)]
+pub struct AuraSensor {
+    pub effect: StatusEffect,
+}
+
 #[derive(Component)]
 pub struct TowerShooter {
     pub cool_down: f32,
     pub rate_of_fire_per_minute: f32,
+    pub projectile: String,
+    pub damage: i32,
+    pub lead: bool,
+    pub anti_air: bool,
 }
 
 impl TowerShooter {
-    pub fn new(rate_of_fire_per_minute: f32) -> Self {
+    pub fn new(weapon: &TowerWeapon) -> Self {
         Self {
             cool_down: 0.0,
-            rate_of_fire_per_minute,
+            rate_of_fire_per_minute: weapon.fire_rate_per_minute,
+            projectile: weapon.projectile.clone(),
+            damage: weapon.damage,
+            lead: weapon.lead,
+            anti_air: weapon.anti_air,
         }
     }
+
+    /// Swaps in a new weapon's stats without resetting the cool down.
+    pub fn set_weapon(&mut self, weapon: &TowerWeapon) {
+        *self = Self { cool_down: self.cool_down, ..Self::new(weapon) };
+    }
+}
+
+/// A walkable trap, removed once `triggers_left` runs out.
+#[derive(Component)]
+pub struct Trap {
+    pub damage: i32,
+    pub effect: Option<StatusEffect>,
+    pub triggers_left: u32,
+    pub rearm: f32,
+    /// Seconds until it can trigger again.
+    pub cool_down: f32,
+}
+
+impl Trap {
+    pub fn new(trap: &TowerTrap) -> Self {
+        Self {
+            damage: trap.damage,
+            effect: trap.effect.clone(),
+            triggers_left: trap.triggers,
+            rearm: trap.rearm,
+            cool_down: 0.0,
+        }
+    }
+
+    /// Swaps in an upgraded trap. Triggers already used stay used.
+    pub fn set_trap(&mut self, previous: &TowerTrap, trap: &TowerTrap) {
+        let used = previous.triggers.saturating_sub(self.triggers_left);
+        *self = Self {
+            triggers_left: trap.triggers.saturating_sub(used),
+            cool_down: self.cool_down,
+            ..Self::new(trap)
+        };
+    }
+}
+
+/// Which `TowerDef` a built piece came from and how many times it has been upgraded.
+#[derive(Component, Clone, Debug)]
+pub struct TowerLevel {
+    pub key: String,
+    pub tier: usize,
 }
 
 impl CoolDown for TowerShooter {
@@ -30,3 +92,48 @@ impl CoolDown for TowerShooter {
         }
     }
 }
+
+/// Which alien in range a tower shoots at. Lives on the tower itself; cycle it with [T] in build mode.
+#[derive(Component, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TargetingMode {
+    #[default]
+    Closest,
+    /// Furthest along its route, i.e. cheapest remaining flow-field cost to the goal.
+    First,
+    Last,
+    Strongest,
+    Weakest,
+}
+
+impl TargetingMode {
+    pub fn next(self) -> Self {
+        match self {
+            TargetingMode::Closest => TargetingMode::First,
+            TargetingMode::First => TargetingMode::Last,
+            TargetingMode::Last => TargetingMode::Strongest,
+            TargetingMode::Strongest => TargetingMode::Weakest,
+            TargetingMode::Weakest => TargetingMode::Closest,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TargetingMode::Closest => "Closest",
+            TargetingMode::First => "First",
+            TargetingMode::Last => "Last",
+            TargetingMode::Strongest => "Strongest",
+            TargetingMode::Weakest => "Weakest",
+        }
+    }
+
+    /// Sort key for a candidate; the tower fires at the smallest.
+    pub fn rank(self, distance_squared: f32, health: i32, cost_to_goal: u32) -> f32 {
+        match self {
+            TargetingMode::Closest => distance_squared,
+            TargetingMode::First => cost_to_goal as f32,
+            TargetingMode::Last => -(cost_to_goal as f32),
+            TargetingMode::Strongest => -(health as f32),
+            TargetingMode::Weakest => health as f32,
+        }
+    }
+}
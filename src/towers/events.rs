@@ -4,5 +4,5 @@ use bevy::prelude::Message;
 #[derive(Message, Clone)]
 pub struct BuildTower {
     pub position: Vec3,
-    pub model_definition_key: &'static str,
+    pub model_definition_key: String,
 }
@@ -1,3 +1,4 @@
 pub(crate) mod systems;
 pub(crate) mod components;
-pub(crate) mod events;
\ No newline at end of file
+pub(crate) mod events;
+pub(crate) mod tower_defs;
\ No newline at end of file
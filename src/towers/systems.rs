@@ -1,24 +1,38 @@
-use bevy::prelude::Name;
-use bevy::math::Vec3;
-use bevy::prelude::{Commands, Query, Res, Transform, With};
+use bevy::log::info;
+use bevy::math::{Vec3, Vec3Swizzles};
+use bevy::asset::AssetServer;
+use bevy::prelude::{ChildOf, Children, Commands, Entity, Has, MessageReader, MessageWriter, Query, Res, ResMut, With, Without};
 use bevy::scene::SceneRoot;
 use bevy::time::Time;
-use avian3d::prelude::{Collider, CollidingEntities, CollisionLayers, LinearVelocity, Position, RigidBody};
-use bevy_wind_waker_shader::WindWakerShaderBuilder;
-use crate::alien::components::general::Alien;
-use crate::general::components::{Ball, CollisionLayer};
-use crate::general::components::map_components::CoolDown;
-use crate::towers::components::{TowerSensor, TowerShooter};
-use crate::assets::assets_plugin::GameAssets;
+use avian3d::prelude::{Collider, CollidingEntities, LinearVelocity, Position};
+use crate::alien::components::general::{Alien, AlienCounter, Bounty, Flyer};
+use crate::building::systems::ToWorldCoordinates;
+use crate::economy::resources::Wallet;
+use crate::game_state::score_keeper::GameTrackingEvent;
+use crate::general::components::Health;
+use crate::general::components::map_components::{CoolDown, CurrentTile, Terrain};
+use crate::general::systems::map_systems::TileDefinitions;
+use crate::map::flow_field::FlowField;
+use crate::player::components::{BuildingIndicator, IsBuildIndicator, IsBuilding, IsObstacle};
+use crate::player::events::building_events::{CycleTargeting, UpgradeTower};
+use crate::projectiles::aiming::lead_point;
+use crate::projectiles::events::FireProjectile;
+use crate::projectiles::projectile_defs::ProjectileDefs;
+use crate::status_effects::events::ApplyStatus;
+use crate::towers::components::{AuraSensor, TargetingMode, TowerLevel, TowerSensor, TowerShooter, Trap};
+use crate::towers::tower_defs::TowerDefs;
 
 pub fn shoot_alien_system(
-    mut commands: Commands,
-    mut tower_query: Query<(&Position, &CollidingEntities, &mut TowerShooter), With<TowerSensor>>,
-    alien_query: Query<&Position, With<Alien>>,
+    mut fire_projectile_mw: MessageWriter<FireProjectile>,
+    mut tower_query: Query<(&Position, &CollidingEntities, &mut TowerShooter, &ChildOf), With<TowerSensor>>,
+    targeting_query: Query<&TargetingMode>,
+    alien_query: Query<(&Position, &LinearVelocity, &Health, &CurrentTile, Has<Flyer>), With<Alien>>,
+    flow_field: Res<FlowField>,
+    tile_definitions: Res<TileDefinitions>,
+    projectile_defs: Res<ProjectileDefs>,
     time: Res<Time>,
-    game_assets: Res<GameAssets>,
 ) {
-    for (tower_position, colliding_entities, mut tower_shooter) in tower_query.iter_mut() {
+    for (tower_position, colliding_entities, mut tower_shooter, child_of) in tower_query.iter_mut() {
         // Check if any alien is in range
         let has_alien = colliding_entities.iter().any(|e| alien_query.contains(*e));
         if !has_alien {
@@ -26,39 +40,183 @@ pub fn shoot_alien_system(
         }
 
         if tower_shooter.cool_down(time.delta_secs()) {
-            let closest_alien = colliding_entities.iter().filter_map(|e| {
-                alien_query.get(*e).ok().map(|pos| (*e, pos))
-            }).min_by(|(_, a_pos), (_, b_pos)| {
-                let a_dist = (a_pos.0 - tower_position.0).length_squared();
-                let b_dist = (b_pos.0 - tower_position.0).length_squared();
-                a_dist.partial_cmp(&b_dist).unwrap()
+            let targeting = targeting_query.get(child_of.parent()).copied().unwrap_or_default();
+            let goal_position = flow_field.goal.to_world_coords(&tile_definitions).xz();
+            let target_alien = colliding_entities.iter().filter_map(|e| {
+                alien_query.get(*e).ok().map(|(pos, velocity, health, tile, is_flyer)| {
+                    // Flyers ignore the ground path, so they are as far as the crow flies,
+                    // counted as plain floor to compare with walkers.
+                    let cost_to_goal = if is_flyer {
+                        let tiles = pos.0.xz().distance(goal_position) / tile_definitions.tile_size;
+                        (tiles * Terrain::Floor.move_cost() as f32) as u32
+                    } else {
+                        flow_field.cost_to_goal(tile.tile)
+                    };
+                    let rank = targeting.rank((pos.0 - tower_position.0).length_squared(), health.health, cost_to_goal);
+                    (*e, pos, velocity, rank)
+                })
+            }).min_by(|(_, _, _, a_rank), (_, _, _, b_rank)| a_rank.total_cmp(b_rank));
+
+            if let Some((alien, alien_position, alien_velocity, _)) = target_alien {
+                let def = projectile_defs.get(&tower_shooter.projectile);
+                let strength = if tower_shooter.lead { 1.0 } else { 0.0 };
+                // Lead from where the shot leaves the tower, not from its centre.
+                let muzzle = tower_position.0
+                    + (alien_position.0 - tower_position.0).normalize_or_zero()
+                    + Vec3::new(0.0, 0.25, 0.0);
+                let aim_point = lead_point(def, muzzle, alien_position.0, alien_velocity.0, strength);
+                let direction = (aim_point - muzzle).normalize_or_zero();
+                fire_projectile_mw.write(FireProjectile {
+                    projectile: tower_shooter.projectile.clone(),
+                    shooter: child_of.parent(),
+                    origin: muzzle,
+                    direction,
+                    target: Some(alien),
+                    aim_point: Some(aim_point),
+                    damage: Some(tower_shooter.damage),
+                    hostile: false,
+                    anti_air: tower_shooter.anti_air,
+                });
+            }
+        }
+    }
+}
+
+/// Keeps each aura's effect on every alien inside it. Effects outlast the field by their
+/// duration, so a short one wears off soon after the alien walks out.
+pub fn aura_tower_system(
+    mut apply_status_mw: MessageWriter<ApplyStatus>,
+    aura_query: Query<(&Position, &CollidingEntities, &AuraSensor, &ChildOf)>,
+    alien_query: Query<&Position, With<Alien>>,
+) {
+    for (aura_position, colliding_entities, aura, child_of) in aura_query.iter() {
+        for alien in colliding_entities.iter() {
+            let Ok(alien_position) = alien_query.get(*alien) else { continue };
+            apply_status_mw.write(ApplyStatus {
+                target: *alien,
+                source: child_of.parent(),
+                effect: aura.effect.clone(),
+                direction: alien_position.0 - aura_position.0,
             });
+        }
+    }
+}
+
+/// Springs every armed trap with aliens on it, hitting all of them with its damage and
+/// effect. A worn-out trap is removed; its tile never left the path, so there is nothing to add back.
+pub fn trap_system(
+    mut commands: Commands,
+    mut trap_query: Query<(Entity, &mut Trap, &CollidingEntities, &Position)>,
+    mut alien_query: Query<(&Position, &mut Health, &Bounty), With<Alien>>,
+    mut alien_counter: ResMut<AlienCounter>,
+    mut game_mw: MessageWriter<GameTrackingEvent>,
+    mut apply_status_mw: MessageWriter<ApplyStatus>,
+    time: Res<Time>,
+) {
+    for (trap_entity, mut trap, colliding_entities, trap_position) in trap_query.iter_mut() {
+        trap.cool_down -= time.delta_secs();
+        if trap.cool_down > 0.0 {
+            continue;
+        }
+        let mut triggered = false;
+        for alien in colliding_entities.iter() {
+            let Ok((alien_position, mut health, bounty)) = alien_query.get_mut(*alien) else { continue };
+            triggered = true;
+            if trap.damage > 0 && health.take_damage(trap.damage) {
+                game_mw.write(GameTrackingEvent::AlienKilled { by: trap_entity, bounty: bounty.0 });
+                alien_counter.count -= 1;
+            }
+            if let Some(effect) = &trap.effect {
+                apply_status_mw.write(ApplyStatus {
+                    target: *alien,
+                    source: trap_entity,
+                    effect: effect.clone(),
+                    direction: alien_position.0 - trap_position.0,
+                });
+            }
+        }
+        if !triggered {
+            continue;
+        }
+        trap.cool_down = trap.rearm;
+        trap.triggers_left = trap.triggers_left.saturating_sub(1);
+        if trap.triggers_left == 0 {
+            info!("Trap at {:?} wore out", trap_position.0);
+            commands.entity(trap_entity).despawn();
+        }
+    }
+}
+
+/// Cycles the targeting mode of the tower under the builder's indicator.
+pub fn cycle_tower_targeting(
+    mut cycle_targeting_mr: MessageReader<CycleTargeting>,
+    builder_query: Query<&BuildingIndicator, With<IsBuilding>>,
+    indicator_query: Query<&CurrentTile, With<IsBuildIndicator>>,
+    mut tower_query: Query<(&CurrentTile, &mut TargetingMode), With<IsObstacle>>,
+) {
+    for CycleTargeting(builder) in cycle_targeting_mr.read() {
+        let Ok(building_indicator) = builder_query.get(*builder) else { continue };
+        let Ok(indicator_tile) = indicator_query.get(building_indicator.0) else { continue };
+        if let Some((_, mut targeting)) = tower_query.iter_mut().find(|(tile, _)| tile.tile == indicator_tile.tile) {
+            *targeting = targeting.next();
+            info!("Tower at {:?} now targets {}", indicator_tile.tile, targeting.label());
+        }
+    }
+}
+
+/// Moves the tower under the builder's indicator to its next tier in place: same entity and
+/// tile, new model and weapon, health scaled to keep the same ratio.
+#[allow(clippy::too_many_arguments)]
+pub fn upgrade_tower_system(
+    mut upgrade_tower_mr: MessageReader<UpgradeTower>,
+    builder_query: Query<&BuildingIndicator, With<IsBuilding>>,
+    indicator_query: Query<&CurrentTile, With<IsBuildIndicator>>,
+    mut tower_query: Query<(&CurrentTile, &mut TowerLevel, &mut Health, &mut SceneRoot, Option<&Children>, Option<&mut Trap>), With<IsObstacle>>,
+    mut sensor_query: Query<(&mut Collider, &mut TowerShooter), With<TowerSensor>>,
+    mut aura_query: Query<&mut Collider, (With<AuraSensor>, Without<TowerSensor>)>,
+    tower_defs: Res<TowerDefs>,
+    mut wallet: ResMut<Wallet>,
+    asset_server: Res<AssetServer>,
+) {
+    for UpgradeTower(builder) in upgrade_tower_mr.read() {
+        let Ok(building_indicator) = builder_query.get(*builder) else { continue };
+        let Ok(indicator_tile) = indicator_query.get(building_indicator.0) else { continue };
+        let Some((_, mut level, mut health, mut scene, children, trap)) = tower_query.iter_mut()
+            .find(|(tile, ..)| tile.tile == indicator_tile.tile) else { continue };
+        let Some(tower_def) = tower_defs.get(&level.key) else { continue };
+        let Some(next) = tower_def.tiers.get(level.tier) else {
+            info!("{} is already at its top tier", tower_def.name);
+            continue;
+        };
+        if !wallet.spend(next.cost) {
+            info!("Upgrading {} costs {}, only {} left", tower_def.name, next.cost, wallet.balance);
+            continue;
+        }
 
-            if let Some((_, alien_position)) = closest_alien {
-                let direction = (alien_position.0 - tower_position.0).normalize();
-                let launch_p = tower_position.0 + direction + Vec3::new(0.0, 0.25, 0.0);
+        let current = tower_def.stats(level.tier);
+        level.tier += 1;
+        let upgraded = tower_def.stats(level.tier);
+        info!("Upgraded {} to tier {} (cost {})", tower_def.name, level.tier, next.cost);
 
-                let entity = commands.spawn((
-                    Name::from("Ball"),
-                    SceneRoot(game_assets.ball_scene.clone()),
-                    Transform::from_xyz(launch_p.x, launch_p.y, launch_p.z),
-                    RigidBody::Dynamic,
-                    Collider::sphere(1.0 / 16.0),
-                    WindWakerShaderBuilder::default().build(),
-                    LinearVelocity(direction * 12.0),
-                    CollisionLayers::new(
-                        [CollisionLayer::Ball],
-                        [
-                            CollisionLayer::Impassable,
-                            CollisionLayer::Floor,
-                            CollisionLayer::Alien,
-                            CollisionLayer::Player,
-                            CollisionLayer::AlienSpawnPoint,
-                            CollisionLayer::AlienGoal
-                        ]),
-                )).id();
+        if upgraded.model != current.model {
+            scene.0 = asset_server.load(upgraded.model.clone());
+        }
+        if let (Some(mut trap), Some(previous), Some(upgraded_trap)) = (trap, &current.trap, &upgraded.trap) {
+            trap.set_trap(previous, upgraded_trap);
+        }
+        let ratio = health.health as f32 / health.max_health.max(1) as f32;
+        health.max_health = upgraded.health;
+        health.health = ((upgraded.health as f32 * ratio).round() as i32).max(1);
 
-                commands.entity(entity).insert(Ball::new(entity));
+        for child in children.into_iter().flatten() {
+            if let Some(weapon) = &upgraded.weapon
+                && let Ok((mut collider, mut shooter)) = sensor_query.get_mut(*child) {
+                *collider = Collider::cylinder(weapon.range, 2.0);
+                shooter.set_weapon(weapon);
+            }
+            if let Some(aura) = &upgraded.aura
+                && let Ok(mut collider) = aura_query.get_mut(*child) {
+                *collider = Collider::cylinder(aura.range, 2.0);
             }
         }
     }
@@ -0,0 +1,268 @@
+use std::path::Path;
+use avian3d::prelude::{CollisionLayers, LayerMask, RigidBody};
+use bevy::prelude::Resource;
+use serde::Deserialize;
+use crate::general::components::CollisionLayer;
+use crate::general::ron_dir::load_ron_dir;
+use crate::general::components::map_components::ModelDefinition;
+use crate::status_effects::components::StatusEffect;
+use crate::towers::components::TargetingMode;
+
+pub const TOWERS_DIR: &str = "assets/towers";
+
+/// Share of what was spent on a piece that selling it gives back.
+pub const SELL_REFUND: f32 = 0.5;
+
+fn default_projectile() -> String { "ball".to_string() }
+fn default_lead() -> bool { true }
+fn default_rearm() -> f32 { 1.0 }
+
+/// The shooting half of a tower. Pieces without one are plain obstacles.
+#[derive(Deserialize, Clone, Debug)]
+pub struct TowerWeapon {
+    /// Radius of the range sensor.
+    pub range: f32,
+    pub fire_rate_per_minute: f32,
+    /// Key of a `ProjectileDef` in `assets/projectiles/`.
+    #[serde(default = "default_projectile")]
+    pub projectile: String,
+    pub damage: i32,
+    /// Mode a freshly built tower starts in.
+    #[serde(default)]
+    pub targeting: TargetingMode,
+    /// Aim where the alien will be when the shot arrives rather than where it is.
+    #[serde(default = "default_lead")]
+    pub lead: bool,
+    /// Sees and hits flyers as well as walking aliens.
+    #[serde(default)]
+    pub anti_air: bool,
+}
+
+impl TowerWeapon {
+    /// The range sensor only notices flyers on anti-air weapons.
+    pub fn sensor_collision_layers(&self) -> CollisionLayers {
+        let mask = if self.anti_air {
+            LayerMask::from([CollisionLayer::Alien, CollisionLayer::Flyer])
+        } else {
+            LayerMask::from([CollisionLayer::Alien])
+        };
+        CollisionLayers::new([CollisionLayer::Sensor], mask)
+    }
+}
+
+/// A field that keeps putting `effect` on every alien inside `range`, with no projectile.
+#[derive(Deserialize, Clone, Debug)]
+pub struct TowerAura {
+    /// Radius of the field sensor.
+    pub range: f32,
+    pub effect: StatusEffect,
+}
+
+/// Makes a piece a walkable trap: its tile stays on the aliens' path, and whatever walks over
+/// it takes `damage` and `effect`. Each trigger hits every alien on it at once.
+#[derive(Deserialize, Clone, Debug)]
+pub struct TowerTrap {
+    #[serde(default)]
+    pub damage: i32,
+    #[serde(default)]
+    pub effect: Option<StatusEffect>,
+    /// Triggers before it wears out and disappears.
+    pub triggers: u32,
+    /// Seconds before it can trigger again.
+    #[serde(default = "default_rearm")]
+    pub rearm: f32,
+}
+
+/// One upgrade step. Fields left out keep the previous tier's value.
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct TowerTier {
+    pub model: Option<String>,
+    pub health: Option<i32>,
+    /// Applies to the weapon and the aura alike.
+    pub range: Option<f32>,
+    pub fire_rate_per_minute: Option<f32>,
+    /// Applies to the weapon and the trap alike.
+    pub damage: Option<i32>,
+    pub triggers: Option<u32>,
+    pub cost: u32,
+}
+
+/// A tower's effective model, health, weapon, aura and trap at one tier.
+#[derive(Clone, Debug)]
+pub struct TowerStats {
+    pub model: String,
+    pub health: i32,
+    pub weapon: Option<TowerWeapon>,
+    pub aura: Option<TowerAura>,
+    pub trap: Option<TowerTrap>,
+}
+
+/// One buildable piece, read from `assets/towers/<key>.ron`.
+#[derive(Deserialize, Clone, Debug)]
+pub struct TowerDef {
+    /// File stem; filled in by `TowerDefs::load`.
+    #[serde(skip)]
+    pub key: String,
+    pub name: String,
+    /// Scene path relative to `assets/`, e.g. `"map/tower_balls.glb#Scene0"`.
+    pub model: String,
+    /// Collider size as `(width, height, depth)`.
+    pub footprint: (f32, f32, f32),
+    pub health: i32,
+    #[serde(default)]
+    pub weapon: Option<TowerWeapon>,
+    #[serde(default)]
+    pub aura: Option<TowerAura>,
+    #[serde(default)]
+    pub trap: Option<TowerTrap>,
+    #[serde(default)]
+    pub cost: u32,
+    /// Position in the build-mode cycle; ties are broken by key.
+    #[serde(default)]
+    pub order: i32,
+    /// Upgrades past the base piece, applied in order.
+    #[serde(default)]
+    pub tiers: Vec<TowerTier>,
+}
+
+impl TowerDef {
+    /// Stats after `tier` upgrades; tier 0 is the piece as built.
+    pub fn stats(&self, tier: usize) -> TowerStats {
+        let mut stats = TowerStats {
+            model: self.model.clone(),
+            health: self.health,
+            weapon: self.weapon.clone(),
+            aura: self.aura.clone(),
+            trap: self.trap.clone(),
+        };
+        for step in self.tiers.iter().take(tier) {
+            if let Some(model) = &step.model {
+                stats.model = model.clone();
+            }
+            if let Some(health) = step.health {
+                stats.health = health;
+            }
+            if let Some(weapon) = &mut stats.weapon {
+                weapon.range = step.range.unwrap_or(weapon.range);
+                weapon.fire_rate_per_minute = step.fire_rate_per_minute.unwrap_or(weapon.fire_rate_per_minute);
+                weapon.damage = step.damage.unwrap_or(weapon.damage);
+            }
+            if let Some(aura) = &mut stats.aura {
+                aura.range = step.range.unwrap_or(aura.range);
+            }
+            if let Some(trap) = &mut stats.trap {
+                trap.damage = step.damage.unwrap_or(trap.damage);
+                trap.triggers = step.triggers.unwrap_or(trap.triggers);
+            }
+        }
+        stats
+    }
+
+    /// What building this piece and buying its first `tier` upgrades cost.
+    pub fn invested(&self, tier: usize) -> u32 {
+        self.cost + self.tiers.iter().take(tier).map(|step| step.cost).sum::<u32>()
+    }
+
+    pub fn sell_value(&self, tier: usize) -> u32 {
+        (self.invested(tier) as f32 * SELL_REFUND).floor() as u32
+    }
+
+    /// Restoring a piece costs the missing share of what was spent on it.
+    pub fn repair_cost(&self, tier: usize, health: i32, max_health: i32) -> u32 {
+        let missing = (max_health - health).max(0) as f32 / max_health.max(1) as f32;
+        (self.invested(tier) as f32 * missing).ceil() as u32
+    }
+
+    /// Traps leave their tile open; everything else takes it off the path.
+    pub fn walkable(&self) -> bool {
+        self.trap.is_some()
+    }
+
+    pub fn model_definition(&self) -> ModelDefinition {
+        let (rigid_body, group, mask) = if self.walkable() {
+            (RigidBody::Static, LayerMask::from([CollisionLayer::Sensor]), LayerMask::from([CollisionLayer::Alien]))
+        } else {
+            (
+                RigidBody::Kinematic,
+                LayerMask::from([CollisionLayer::Impassable]),
+                LayerMask::from([CollisionLayer::Ball, CollisionLayer::Alien, CollisionLayer::Player]),
+            )
+        };
+        ModelDefinition {
+            name: self.name.clone(),
+            file: self.model.clone(),
+            width: self.footprint.0,
+            height: self.footprint.1,
+            depth: self.footprint.2,
+            rigid_body,
+            group,
+            mask,
+        }
+    }
+}
+
+/// Every buildable piece, in build-mode order.
+#[derive(Resource, Debug)]
+pub struct TowerDefs {
+    pub defs: Vec<TowerDef>,
+}
+
+impl TowerDefs {
+    /// Reads every `*.ron` in `TOWERS_DIR`. Unparseable files are logged and skipped; with none
+    /// left it falls back to the built-in obstacle and ball tower so the game still starts.
+    pub fn load() -> Self {
+        let mut defs: Vec<TowerDef> = load_ron_dir(Path::new(TOWERS_DIR))
+            .into_iter()
+            .map(|(key, def)| TowerDef { key, ..def })
+            .collect();
+        if defs.is_empty() {
+            defs = Self::built_in();
+        }
+        defs.sort_by(|a, b| (a.order, &a.key).cmp(&(b.order, &b.key)));
+        Self { defs }
+    }
+
+    fn built_in() -> Vec<TowerDef> {
+        vec![
+            TowerDef {
+                key: "obstacle".to_string(),
+                name: "obstacle".to_string(),
+                model: "map/obstacle.glb#Scene0".to_string(),
+                footprint: (16.0, 4.0, 16.0),
+                health: 100,
+                weapon: None,
+                aura: None,
+                trap: None,
+                cost: 0,
+                order: 0,
+                tiers: Vec::new(),
+            },
+            TowerDef {
+                key: "tower".to_string(),
+                name: "tower".to_string(),
+                model: "map/tower_balls.glb#Scene0".to_string(),
+                footprint: (16.0, 8.0, 16.0),
+                health: 100,
+                weapon: Some(TowerWeapon {
+                    range: 0.5,
+                    fire_rate_per_minute: 20.0,
+                    projectile: default_projectile(),
+                    damage: 10,
+                    targeting: TargetingMode::Closest,
+                    lead: default_lead(),
+                    anti_air: false,
+                }),
+                aura: None,
+                trap: None,
+                cost: 0,
+                order: 1,
+                tiers: Vec::new(),
+            },
+        ]
+    }
+
+    pub fn get(&self, key: &str) -> Option<&TowerDef> {
+        self.defs.iter().find(|def| def.key == key)
+    }
+}
@@ -2,8 +2,16 @@ use crate::alien::components::general::AlienCounter;
 use crate::animation::animation_plugin::{AnimationKey, ANIM_KEYS};
 use crate::game_state::GameState;
 use crate::general::components::Health;
-use crate::player::components::IsBuilding;
+use crate::building::systems::{build_blocked_reason, selected_piece, RouteBlockCache, RoutePreview};
+use crate::game_state::score_keeper::{LevelTracker, WavePhase};
+use crate::general::components::map_components::{CurrentTile, MapModelDefinitions};
+use crate::general::resources::map_resources::MapGraph;
+use crate::player::components::{BuildingIndicator, IsBuildIndicator, IsBuilding, IsObstacle};
+use crate::towers::components::{TargetingMode, TowerLevel, Trap};
+use crate::towers::tower_defs::TowerDefs;
+use crate::economy::resources::Wallet;
 use crate::settings::resources::{GameSettings, ProjectionMode};
+use crate::status_effects::components::StatusEffects;
 use crate::model_settings::resources::{CharacterFolder, ModelSettings, PlayerAnimClips};
 use bevy::prelude::*;
 use bevy::ui_widgets::Activate;
@@ -109,6 +117,14 @@ pub fn spawn_menu(commands: Commands, theme: Res<LavaTheme>) {
         },
     );
 
+    ui.add_button_observe(
+        "Level Editor",
+        |btn| { btn.size_px(220.0, 52.0).font_size(20.0); },
+        |_: On<Activate>, mut next_state: ResMut<NextState<GameState>>| {
+            next_state.set(GameState::LevelEditor);
+        },
+    );
+
     ui.build();
 }
 
@@ -594,9 +610,19 @@ pub fn update_anim_mapping_labels(
 }
 
 
+#[allow(clippy::too_many_arguments)]
 pub fn update_hud(
     alien_counter: Option<Res<AlienCounter>>,
-    building_query: Query<(), With<IsBuilding>>,
+    building_query: Query<&BuildingIndicator, With<IsBuilding>>,
+    indicator_query: Query<&CurrentTile, With<IsBuildIndicator>>,
+    map_graph: Res<MapGraph>,
+    route_block_cache: Res<RouteBlockCache>,
+    level_tracker: Res<LevelTracker>,
+    route_preview: Res<RoutePreview>,
+    tower_query: Query<(&CurrentTile, &TowerLevel, &Health, Option<&TargetingMode>, Option<&Trap>), With<IsObstacle>>,
+    tower_defs: Res<TowerDefs>,
+    model_defs: Res<MapModelDefinitions>,
+    wallet: Res<Wallet>,
     settings: Res<GameSettings>,
     mut alien_text: Query<
         &mut Text,
@@ -625,12 +651,58 @@ pub fn update_hud(
 ) {
     if let Some(counter) = alien_counter
         && let Ok(mut t) = alien_text.single_mut() {
-            **t = format!("Aliens: {}", counter.count);
+            let mut text = format!("Aliens: {}  Credits: {}", counter.count, wallet.balance);
+            if level_tracker.has_waves() {
+                text.push_str(&format!("  Wave {}/{}", level_tracker.wave, level_tracker.waves.len()));
+                if let WavePhase::Building { remaining } = level_tracker.wave_phase {
+                    text.push_str(&format!("  Next in {:.0}s [Enter] call early", remaining.max(0.0)));
+                }
+            }
+            **t = text;
         }
 
     if let Ok(mut t) = build_text.single_mut() {
-        **t = if building_query.iter().next().is_some() {
-            "[BUILD MODE]".to_string()
+        **t = if let Some(building_indicator) = building_query.iter().next() {
+            let indicator_tile = indicator_query.iter().next().map(|tile| tile.tile);
+            let tower = tower_query.iter()
+                .find(|(tile, ..)| Some(tile.tile) == indicator_tile)
+                .and_then(|(_, level, health, targeting, trap)| {
+                    let tower_def = tower_defs.get(&level.key)?;
+                    let max_tier = tower_def.tiers.len();
+                    let mut text = format!("Tier {}/{max_tier}", level.tier);
+                    if let Some(next) = tower_def.tiers.get(level.tier) {
+                        text.push_str(&format!(" [U] upgrade -{}", next.cost));
+                    }
+                    if let Some(targeting) = targeting {
+                        text.push_str(&format!("  Targets {} [T]", targeting.label()));
+                    }
+                    if let Some(trap) = trap {
+                        text.push_str(&format!("  Triggers left {}", trap.triggers_left));
+                    }
+                    text.push_str(&format!("  [F] sell +{}", tower_def.sell_value(level.tier)));
+                    if health.health < health.max_health {
+                        text.push_str(&format!("  [R] repair -{}", tower_def.repair_cost(level.tier, health.health, health.max_health)));
+                    }
+                    text.push_str("  [Del] demolish");
+                    Some(text)
+                });
+            let piece = selected_piece(building_indicator, &model_defs, &tower_defs);
+            let blocked = indicator_tile
+                .and_then(|tile| build_blocked_reason(tile, piece, &map_graph, &route_block_cache, &level_tracker, &wallet));
+            let header = match piece {
+                Some(piece) => format!("[BUILD MODE] {} ({})", piece.name, piece.cost),
+                None => "[BUILD MODE]".to_string(),
+            };
+            match (tower, blocked, route_preview.lengths()) {
+                (Some(tower), _, _) => format!("{header} {tower}"),
+                (None, Some(reason), _) => format!("{header} Can't build here: {reason}"),
+                (None, None, Some((now, Some(after)))) => format!(
+                    "{header} Route: {now} -> {after} tiles ({:+})",
+                    after as i64 - now as i64,
+                ),
+                (None, None, Some((now, None))) => format!("{header} Route: {now} tiles -> sealed"),
+                (None, None, None) => header,
+            }
         } else {
             String::new()
         };
@@ -679,9 +751,30 @@ pub fn add_health_bar(mut commands: Commands, mut add_health_bar_mr: MessageRead
             .entity(bar)
             .entry::<Node>()
             .and_modify(|mut n| n.position_type = PositionType::Absolute);
+        commands.spawn((
+            StatusLabel,
+            WorldFollower {
+                target,
+                offset: Vec2::new(-30.0, -54.0),
+            },
+            Text::new(""),
+            TextFont {
+                font_size: 10.0,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+            Node {
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+        ));
     }
 }
 
+/// Sits just above a health bar and names the status effects on its target.
+#[derive(Component)]
+pub struct StatusLabel;
+
 pub fn sync_health_bars(
     mut bars: Query<(&WorldFollower, &mut ProgressBar)>,
     health_query: Query<&Health>,
@@ -692,3 +785,25 @@ pub fn sync_health_bars(
         }
     }
 }
+
+pub fn sync_status_labels(
+    mut labels: Query<(&WorldFollower, &mut Text, &mut TextColor), With<StatusLabel>>,
+    status_query: Query<&StatusEffects>,
+) {
+    for (follower, mut text, mut color) in labels.iter_mut() {
+        let Ok(status_effects) = status_query.get(follower.target) else { continue };
+        let label = status_effects.effects.iter()
+            .map(|active| match active.stacks {
+                1 => active.effect.kind.label().to_string(),
+                stacks => format!("{} x{}", active.effect.kind.label(), stacks),
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        if text.0 != label {
+            text.0 = label;
+        }
+        if let Some(first) = status_effects.effects.first() {
+            color.0 = first.effect.kind.color();
+        }
+    }
+}
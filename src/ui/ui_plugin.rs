@@ -5,7 +5,7 @@ use lava_ui_builder::LavaUiPlugin;
 use crate::game_state::GameState;
 use crate::ui::spawn_ui::{
     add_health_bar, cleanup_state, game_theme, goto_state_system, GotoState,
-    spawn_menu, spawn_showcase_ui, spawn_ui, sync_health_bars,
+    spawn_menu, spawn_showcase_ui, spawn_ui, sync_health_bars, sync_status_labels,
     toggle_settings_panel, toggle_model_panel,
     update_camera_panel, update_model_labels, update_anim_mapping_labels,
     update_hud,
@@ -33,6 +33,7 @@ impl Plugin for UiPlugin {
                     goto_state_system,
                     add_health_bar,
                     sync_health_bars,
+                    sync_status_labels,
                     update_hud,
                     toggle_settings_panel,
                     toggle_model_panel,